@@ -15,6 +15,7 @@ mod unit_tests;
 
 pub mod ed25519;
 pub mod hash;
+pub mod multi_ed25519;
 pub mod serde_name;
 #[cfg(test)]
 pub mod test_utils;
@@ -0,0 +1,312 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module provides an API for the accountable threshold multi-sig PureEdDSA signature scheme
+//! over the ed25519 twisted Edwards curve as defined in [RFC8032](https://tools.ietf.org/html/rfc8032).
+//!
+//! The encoding is compatible with the Diem `MultiEd25519` scheme:
+//! * a public key is the concatenation of up to 32 ed25519 public keys followed by the
+//!   threshold byte;
+//! * a signature is the concatenation of the ed25519 signatures ordered by the index of the
+//!   signer followed by a 4-byte bitmap of the signers. The bit `i` (counting from the most
+//!   significant bit of the first byte) is set if the key `i` signed the message.
+#![allow(clippy::integer_arithmetic)]
+
+use crate::{
+    ed25519::{
+        Ed25519PublicKey, Ed25519Signature, ED25519_PUBLIC_KEY_LENGTH, ED25519_SIGNATURE_LENGTH,
+    },
+    traits::*,
+};
+use alloc::vec::Vec;
+use anyhow::{bail, Result};
+use core::convert::TryFrom;
+use core::fmt;
+
+/// Maximum number of keys in a multi-Ed25519 public key.
+pub const MAX_NUM_OF_KEYS: usize = 32;
+
+/// Number of bytes of the signers bitmap.
+pub const BITMAP_NUM_OF_BYTES: usize = 4;
+
+/// A K-of-N multi-Ed25519 public key.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiEd25519PublicKey {
+    public_keys: Vec<Ed25519PublicKey>,
+    threshold: u8,
+}
+
+/// A multi-Ed25519 signature with the bitmap of the signers.
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiEd25519Signature {
+    signatures: Vec<Ed25519Signature>,
+    bitmap: [u8; BITMAP_NUM_OF_BYTES],
+}
+
+impl MultiEd25519PublicKey {
+    /// Creates a K-of-N public key, where K is `threshold` and N is the number of `public_keys`.
+    pub fn new(
+        public_keys: Vec<Ed25519PublicKey>,
+        threshold: u8,
+    ) -> core::result::Result<MultiEd25519PublicKey, CryptoMaterialError> {
+        let num_of_keys = public_keys.len();
+        if num_of_keys == 0 || num_of_keys > MAX_NUM_OF_KEYS {
+            Err(CryptoMaterialError::WrongLengthError)
+        } else if threshold == 0 || num_of_keys < threshold as usize {
+            Err(CryptoMaterialError::ValidationError)
+        } else {
+            Ok(MultiEd25519PublicKey {
+                public_keys,
+                threshold,
+            })
+        }
+    }
+
+    /// Returns the public keys.
+    pub fn public_keys(&self) -> &[Ed25519PublicKey] {
+        &self.public_keys
+    }
+
+    /// Returns the number of signatures required to pass the verification.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Serialize a MultiEd25519PublicKey.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.length());
+        for key in &self.public_keys {
+            bytes.extend_from_slice(&key.to_bytes());
+        }
+        bytes.push(self.threshold);
+        bytes
+    }
+}
+
+impl MultiEd25519Signature {
+    /// Creates a signature from the ed25519 signatures paired with the index of the signer key.
+    pub fn new(
+        mut signatures: Vec<(Ed25519Signature, u8)>,
+    ) -> core::result::Result<MultiEd25519Signature, CryptoMaterialError> {
+        if signatures.is_empty() || signatures.len() > MAX_NUM_OF_KEYS {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        signatures.sort_by_key(|(_, index)| *index);
+
+        let mut bitmap = [0u8; BITMAP_NUM_OF_BYTES];
+        for (_, index) in &signatures {
+            let index = *index as usize;
+            if index >= MAX_NUM_OF_KEYS {
+                return Err(CryptoMaterialError::BitVecError(format!(
+                    "Signature index {} is out of range",
+                    index
+                )));
+            }
+            if bitmap_get_bit(bitmap, index) {
+                return Err(CryptoMaterialError::BitVecError(format!(
+                    "Duplicate signature index {}",
+                    index
+                )));
+            }
+            bitmap_set_bit(&mut bitmap, index);
+        }
+
+        Ok(MultiEd25519Signature {
+            signatures: signatures.into_iter().map(|(sig, _)| sig).collect(),
+            bitmap,
+        })
+    }
+
+    /// Returns the signatures ordered by the index of the signer.
+    pub fn signatures(&self) -> &[Ed25519Signature] {
+        &self.signatures
+    }
+
+    /// Returns the bitmap of the signers.
+    pub fn bitmap(&self) -> &[u8; BITMAP_NUM_OF_BYTES] {
+        &self.bitmap
+    }
+
+    /// Serialize a MultiEd25519Signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.length());
+        for sig in &self.signatures {
+            bytes.extend_from_slice(&sig.to_bytes());
+        }
+        bytes.extend_from_slice(&self.bitmap);
+        bytes
+    }
+
+    /// Checks that `self` is valid for an arbitrary &[u8] `message` using `public_key`:
+    /// every signature must be valid for the key marked in the bitmap and the number of
+    /// signatures must reach the threshold of the key.
+    pub fn verify_arbitrary_msg(
+        &self,
+        message: &[u8],
+        public_key: &MultiEd25519PublicKey,
+    ) -> Result<()> {
+        match bitmap_last_set_bit(self.bitmap) {
+            Some(last_bit) if (last_bit as usize) < public_key.public_keys.len() => (),
+            _ => bail!(
+                "Signature index is out of public keys range: {} keys",
+                public_key.public_keys.len()
+            ),
+        }
+        if self.signatures.len() < public_key.threshold as usize {
+            bail!(
+                "Not enough signatures: expected {}, got {}",
+                public_key.threshold,
+                self.signatures.len()
+            );
+        }
+
+        let mut bitmap_index = 0;
+        for sig in &self.signatures {
+            while !bitmap_get_bit(self.bitmap, bitmap_index) {
+                bitmap_index += 1;
+            }
+            sig.verify_arbitrary_msg(message, &public_key.public_keys[bitmap_index])?;
+            bitmap_index += 1;
+        }
+        Ok(())
+    }
+}
+
+//////////////////////
+// PublicKey Traits //
+//////////////////////
+
+impl TryFrom<&[u8]> for MultiEd25519PublicKey {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a MultiEd25519PublicKey. Every key is checked for validity.
+    fn try_from(bytes: &[u8]) -> core::result::Result<MultiEd25519PublicKey, CryptoMaterialError> {
+        if bytes.len() % ED25519_PUBLIC_KEY_LENGTH != 1 {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let (keys, threshold) = bytes.split_at(bytes.len() - 1);
+        let public_keys = keys
+            .chunks_exact(ED25519_PUBLIC_KEY_LENGTH)
+            .map(Ed25519PublicKey::try_from)
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+        MultiEd25519PublicKey::new(public_keys, threshold[0])
+    }
+}
+
+impl Length for MultiEd25519PublicKey {
+    fn length(&self) -> usize {
+        self.public_keys.len() * ED25519_PUBLIC_KEY_LENGTH + 1
+    }
+}
+
+impl ValidCryptoMaterial for MultiEd25519PublicKey {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+}
+
+impl core::hash::Hash for MultiEd25519PublicKey {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes());
+    }
+}
+
+impl fmt::Display for MultiEd25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for MultiEd25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MultiEd25519PublicKey({})", self)
+    }
+}
+
+//////////////////////
+// Signature Traits //
+//////////////////////
+
+impl TryFrom<&[u8]> for MultiEd25519Signature {
+    type Error = CryptoMaterialError;
+
+    /// Deserialize a MultiEd25519Signature. Every signature is checked for malleability and
+    /// the bitmap must mark exactly as many signers as there are signatures.
+    fn try_from(bytes: &[u8]) -> core::result::Result<MultiEd25519Signature, CryptoMaterialError> {
+        if bytes.len() % ED25519_SIGNATURE_LENGTH != BITMAP_NUM_OF_BYTES {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+        let (sigs, bitmap) = bytes.split_at(bytes.len() - BITMAP_NUM_OF_BYTES);
+        let num_of_sigs = sigs.len() / ED25519_SIGNATURE_LENGTH;
+        if num_of_sigs == 0 || num_of_sigs > MAX_NUM_OF_KEYS {
+            return Err(CryptoMaterialError::WrongLengthError);
+        }
+
+        let bitmap = <[u8; BITMAP_NUM_OF_BYTES]>::try_from(bitmap)
+            .map_err(|_| CryptoMaterialError::WrongLengthError)?;
+        if bitmap_count_ones(bitmap) as usize != num_of_sigs {
+            return Err(CryptoMaterialError::DeserializationError);
+        }
+
+        let signatures = sigs
+            .chunks_exact(ED25519_SIGNATURE_LENGTH)
+            .map(Ed25519Signature::try_from)
+            .collect::<core::result::Result<Vec<_>, _>>()?;
+        Ok(MultiEd25519Signature { signatures, bitmap })
+    }
+}
+
+impl Length for MultiEd25519Signature {
+    fn length(&self) -> usize {
+        self.signatures.len() * ED25519_SIGNATURE_LENGTH + BITMAP_NUM_OF_BYTES
+    }
+}
+
+impl ValidCryptoMaterial for MultiEd25519Signature {
+    fn to_bytes(&self) -> Vec<u8> {
+        self.to_bytes()
+    }
+}
+
+impl core::hash::Hash for MultiEd25519Signature {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        state.write(&self.to_bytes());
+    }
+}
+
+impl fmt::Display for MultiEd25519Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl fmt::Debug for MultiEd25519Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MultiEd25519Signature({})", self)
+    }
+}
+
+////////////////////
+// Bitmap helpers //
+////////////////////
+
+fn bitmap_set_bit(bitmap: &mut [u8; BITMAP_NUM_OF_BYTES], index: usize) {
+    bitmap[index / 8] |= 128 >> (index % 8);
+}
+
+fn bitmap_get_bit(bitmap: [u8; BITMAP_NUM_OF_BYTES], index: usize) -> bool {
+    bitmap[index / 8] & (128 >> (index % 8)) != 0
+}
+
+fn bitmap_count_ones(bitmap: [u8; BITMAP_NUM_OF_BYTES]) -> u32 {
+    bitmap.iter().map(|byte| byte.count_ones()).sum()
+}
+
+fn bitmap_last_set_bit(bitmap: [u8; BITMAP_NUM_OF_BYTES]) -> Option<u8> {
+    bitmap
+        .iter()
+        .rev()
+        .enumerate()
+        .find(|(_, byte)| **byte != 0)
+        .map(|(i, byte)| (8 * (BITMAP_NUM_OF_BYTES - i) - 1 - byte.trailing_zeros() as usize) as u8)
+}
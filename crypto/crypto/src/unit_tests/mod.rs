@@ -3,3 +3,4 @@
 
 mod cryptohasher;
 mod hash_test;
+mod multi_ed25519_test;
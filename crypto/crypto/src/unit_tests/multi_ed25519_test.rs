@@ -0,0 +1,120 @@
+// Copyright (c) The Diem Core Contributors
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    ed25519::{Ed25519PrivateKey, Ed25519PublicKey},
+    multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature},
+    traits::*,
+};
+use core::convert::TryFrom;
+
+const MESSAGE: &[u8] = b"multisig message";
+
+fn private_keys(n: u8) -> Vec<Ed25519PrivateKey> {
+    (1..=n)
+        .map(|i| Ed25519PrivateKey::try_from(&[i; 32][..]).unwrap())
+        .collect()
+}
+
+fn public_key(keys: &[Ed25519PrivateKey], threshold: u8) -> MultiEd25519PublicKey {
+    MultiEd25519PublicKey::new(keys.iter().map(Ed25519PublicKey::from).collect(), threshold)
+        .unwrap()
+}
+
+fn sign(keys: &[Ed25519PrivateKey], signers: &[u8], message: &[u8]) -> MultiEd25519Signature {
+    MultiEd25519Signature::new(
+        signers
+            .iter()
+            .map(|i| (keys[*i as usize].sign_arbitrary_message(message), *i))
+            .collect(),
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_multi_ed25519_encoding() {
+    let keys = private_keys(3);
+    let pk = public_key(&keys, 2);
+    let pk_bytes = pk.to_bytes();
+    assert_eq!(pk_bytes.len(), 3 * 32 + 1);
+    assert_eq!(pk_bytes[96], 2);
+    assert_eq!(MultiEd25519PublicKey::try_from(&pk_bytes[..]).unwrap(), pk);
+
+    let sig = sign(&keys, &[2, 0], MESSAGE);
+    let sig_bytes = sig.to_bytes();
+    assert_eq!(sig_bytes.len(), 2 * 64 + 4);
+    assert_eq!(&sig_bytes[128..], &[0b1010_0000, 0, 0, 0]);
+    assert_eq!(
+        &sig_bytes[..64],
+        &keys[0].sign_arbitrary_message(MESSAGE).to_bytes()[..]
+    );
+    assert_eq!(
+        MultiEd25519Signature::try_from(&sig_bytes[..]).unwrap(),
+        sig
+    );
+}
+
+#[test]
+fn test_multi_ed25519_invalid_encoding() {
+    let keys = private_keys(3);
+    let pk_bytes = public_key(&keys, 2).to_bytes();
+
+    let mut zero_threshold = pk_bytes.clone();
+    zero_threshold[96] = 0;
+    assert!(MultiEd25519PublicKey::try_from(&zero_threshold[..]).is_err());
+
+    let mut big_threshold = pk_bytes.clone();
+    big_threshold[96] = 4;
+    assert!(MultiEd25519PublicKey::try_from(&big_threshold[..]).is_err());
+    assert!(MultiEd25519PublicKey::try_from(&pk_bytes[..96]).is_err());
+    assert!(MultiEd25519PublicKey::try_from(&[1][..]).is_err());
+
+    let sig_bytes = sign(&keys, &[0, 1], MESSAGE).to_bytes();
+    let mut wrong_bitmap = sig_bytes.clone();
+    wrong_bitmap[128] = 0b1110_0000;
+    assert!(MultiEd25519Signature::try_from(&wrong_bitmap[..]).is_err());
+    assert!(MultiEd25519Signature::try_from(&sig_bytes[..128]).is_err());
+    assert!(MultiEd25519Signature::try_from(&sig_bytes[64..]).is_err());
+
+    let sig = keys[0].sign_arbitrary_message(MESSAGE);
+    assert!(MultiEd25519Signature::new(vec![(sig.clone(), 1), (sig.clone(), 1)]).is_err());
+    assert!(MultiEd25519Signature::new(vec![(sig, 32)]).is_err());
+    assert!(MultiEd25519Signature::new(vec![]).is_err());
+}
+
+#[test]
+fn test_multi_ed25519_verify() {
+    let keys = private_keys(3);
+    let pk = public_key(&keys, 2);
+
+    assert!(sign(&keys, &[0, 1], MESSAGE)
+        .verify_arbitrary_msg(MESSAGE, &pk)
+        .is_ok());
+    assert!(sign(&keys, &[2, 0], MESSAGE)
+        .verify_arbitrary_msg(MESSAGE, &pk)
+        .is_ok());
+    assert!(sign(&keys, &[0, 1, 2], MESSAGE)
+        .verify_arbitrary_msg(MESSAGE, &pk)
+        .is_ok());
+
+    // Below the threshold.
+    assert!(sign(&keys, &[1], MESSAGE)
+        .verify_arbitrary_msg(MESSAGE, &pk)
+        .is_err());
+    // Wrong message.
+    assert!(sign(&keys, &[0, 1], MESSAGE)
+        .verify_arbitrary_msg(b"other message", &pk)
+        .is_err());
+    // Signature of a key which is not a part of the public key.
+    let other_keys = private_keys(4);
+    assert!(sign(&other_keys, &[0, 3], MESSAGE)
+        .verify_arbitrary_msg(MESSAGE, &pk)
+        .is_err());
+    // Signatures assigned to the wrong keys.
+    let swapped = MultiEd25519Signature::new(vec![
+        (keys[0].sign_arbitrary_message(MESSAGE), 1),
+        (keys[1].sign_arbitrary_message(MESSAGE), 0),
+    ])
+    .unwrap();
+    assert!(swapped.verify_arbitrary_msg(MESSAGE, &pk).is_err());
+}
@@ -14,23 +14,46 @@ use move_core_types::{
     language_storage::ModuleId,
     vm_status::{self, StatusCode, StatusType, VMStatus},
 };
+use parity_scale_codec_derive::{Decode, Encode};
 
 pub type VMResult<T> = ::core::result::Result<T, VMError>;
 pub type BinaryLoaderResult<T> = ::core::result::Result<T, PartialVMError>;
 pub type PartialVMResult<T> = ::core::result::Result<T, PartialVMError>;
 
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd, Encode, Decode)]
 pub enum Location {
+    #[codec(index = 0)]
     Undefined,
+    #[codec(index = 1)]
     Script,
+    #[codec(index = 2)]
     Module(ModuleId),
 }
 
+/// Snapshot of the call stack at the point of failure.
+#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct ExecutionState {
+    stack_trace: Vec<(Option<ModuleId>, FunctionDefinitionIndex, CodeOffset)>,
+}
+
+impl ExecutionState {
+    pub fn new(stack_trace: Vec<(Option<ModuleId>, FunctionDefinitionIndex, CodeOffset)>) -> Self {
+        Self { stack_trace }
+    }
+
+    /// Returns the module, the function and the code offset of every frame, outermost first.
+    /// The module is `None` for the script.
+    pub fn stack_trace(&self) -> &Vec<(Option<ModuleId>, FunctionDefinitionIndex, CodeOffset)> {
+        &self.stack_trace
+    }
+}
+
 #[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
 pub struct VMError {
     major_status: StatusCode,
     sub_status: Option<u64>,
     message: Option<String>,
+    exec_state: Option<ExecutionState>,
     location: Location,
     indices: Vec<(IndexKind, TableIndex)>,
     offsets: Vec<(FunctionDefinitionIndex, CodeOffset)>,
@@ -107,6 +130,17 @@ impl VMError {
         self.message.as_ref()
     }
 
+    pub fn exec_state(&self) -> Option<&ExecutionState> {
+        self.exec_state.as_ref()
+    }
+
+    pub fn with_exec_state(self, exec_state: ExecutionState) -> Self {
+        Self {
+            exec_state: Some(exec_state),
+            ..self
+        }
+    }
+
     pub fn location(&self) -> &Location {
         &self.location
     }
@@ -129,6 +163,7 @@ impl VMError {
         StatusCode,
         Option<u64>,
         Option<String>,
+        Option<ExecutionState>,
         Location,
         Vec<(IndexKind, TableIndex)>,
         Vec<(FunctionDefinitionIndex, CodeOffset)>,
@@ -137,6 +172,7 @@ impl VMError {
             major_status,
             sub_status,
             message,
+            exec_state,
             location,
             indices,
             offsets,
@@ -145,6 +181,7 @@ impl VMError {
             major_status,
             sub_status,
             message,
+            exec_state,
             location,
             indices,
             offsets,
@@ -193,6 +230,7 @@ impl PartialVMError {
             major_status,
             sub_status,
             message,
+            exec_state: None,
             location,
             indices,
             offsets,
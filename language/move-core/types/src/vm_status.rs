@@ -7,6 +7,8 @@ use crate::language_storage::ModuleId;
 use anyhow::Result;
 use core::{convert::TryFrom, fmt};
 use enum_iterator::IntoEnumIterator;
+use parity_scale_codec::{Decode, Encode, Error as PsError, Input, Output};
+use parity_scale_codec_derive::{Decode, Encode};
 #[cfg(any(test, feature = "fuzzing"))]
 use proptest::prelude::*;
 #[cfg(any(test, feature = "fuzzing"))]
@@ -47,23 +49,27 @@ pub static EXECUTION_STATUS_MAX_CODE: u64 = 4999;
 /// - `Executed` indicating successful execution
 /// - `Error` indicating an error from the VM itself
 /// - `MoveAbort` indicating an `abort` ocurred inside of a Move program
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Encode, Decode)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 #[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
 pub enum VMStatus {
     /// The VM status corresponding to an EXECUTED status code
+    #[codec(index = 0)]
     Executed,
 
     /// Indicates an error from the VM, e.g. OUT_OF_GAS, INVALID_AUTH_KEY, RET_TYPE_MISMATCH_ERROR
     /// etc.
     /// The code will neither EXECUTED nor ABORTED
+    #[codec(index = 1)]
     Error(StatusCode),
 
     /// Indicates an `abort` from inside Move code. Contains the location of the abort and the code
+    #[codec(index = 2)]
     MoveAbort(AbortLocation, /* code */ u64),
 
     /// Indicates an failure from inside Move code, where the VM could not continue exection, e.g.
     /// dividing by zero or a missing resource
+    #[codec(index = 3)]
     ExecutionFailure {
         status_code: StatusCode,
         location: AbortLocation,
@@ -97,13 +103,15 @@ pub type DiscardedVMStatus = StatusCode;
 
 /// An `AbortLocation` specifies where a Move program `abort` occurred, either in a function in
 /// a module, or in a script
-#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, Encode, Decode)]
 #[cfg_attr(any(test, feature = "fuzzing"), derive(Arbitrary))]
 #[cfg_attr(any(test, feature = "fuzzing"), proptest(no_params))]
 pub enum AbortLocation {
     /// Indicates `abort` occurred in the specified module
+    #[codec(index = 0)]
     Module(ModuleId),
     /// Indicates the `abort` occurred in a script
+    #[codec(index = 1)]
     Script,
 }
 
@@ -637,6 +645,10 @@ pub enum StatusCode {
     CALL_STACK_OVERFLOW = 4021,
     VM_MAX_TYPE_DEPTH_REACHED = 4024,
     VM_MAX_VALUE_DEPTH_REACHED = 4025,
+    // The host rejected the native balance operations of the transaction.
+    BALANCE_REJECTED = 4026,
+    // A native balance does not fit the width of the Move balance resource.
+    BALANCE_OUT_OF_RANGE = 4027,
 
     // A reserved status to represent an unknown vm status.
     // this is std::u64::MAX, but we can't pattern match on that, so put the hardcoded value in
@@ -717,6 +729,22 @@ impl<'de> de::Deserialize<'de> for StatusCode {
     }
 }
 
+impl Encode for StatusCode {
+    fn size_hint(&self) -> usize {
+        u64::from(*self).size_hint()
+    }
+
+    fn encode_to<T: Output + ?Sized>(&self, dest: &mut T) {
+        u64::from(*self).encode_to(dest)
+    }
+}
+
+impl Decode for StatusCode {
+    fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, PsError> {
+        StatusCode::try_from(u64::decode(input)?).map_err(|_| "Unknown status code".into())
+    }
+}
+
 impl From<StatusCode> for u64 {
     fn from(status: StatusCode) -> u64 {
         status as u64
@@ -730,6 +758,20 @@ pub mod sub_status {
     pub const INVALID_TYPE_PARAM: u64 = 1;
     // Failure in BCS deserialization
     pub const NFE_BCS_SERIALIZATION_FAILURE: u64 = 0x1C5;
+    // Host query is not supported by the host.
+    pub const NFE_HOST_QUERY_UNSUPPORTED: u64 = 0x1C6;
+    // Failure in BCS deserialization
+    pub const NFE_BCS_DESERIALIZATION_FAILURE: u64 = 0x1C7;
+    // U256 remainder of the division by zero
+    pub const NFE_U256_MOD_BY_ZERO: u64 = 0x1C8;
+    // U256 power does not fit into 256 bits
+    pub const NFE_U256_POW_OVERFLOW: u64 = 0x1C9;
+    // U256 mul_div result does not fit into 256 bits
+    pub const NFE_U256_MUL_DIV_OVERFLOW: u64 = 0x1CA;
+    // U256 mul_div by zero
+    pub const NFE_U256_MUL_DIV_BY_ZERO: u64 = 0x1CB;
+    // U256 is decoded from more than 32 bytes
+    pub const NFE_U256_BYTES_LENGTH: u64 = 0x1CC;
 }
 
 /// The `Arbitrary` impl only generates validation statuses since the full enum is too large.
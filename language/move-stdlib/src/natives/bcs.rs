@@ -4,7 +4,9 @@
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 use move_binary_format::errors::PartialVMResult;
-use move_core_types::vm_status::sub_status::NFE_BCS_SERIALIZATION_FAILURE;
+use move_core_types::vm_status::sub_status::{
+    INVALID_TYPE_PARAM, NFE_BCS_DESERIALIZATION_FAILURE, NFE_BCS_SERIALIZATION_FAILURE,
+};
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
@@ -52,3 +54,41 @@ pub fn native_to_bytes(
         smallvec![Value::vector_u8(serialized_value)],
     ))
 }
+
+/// Rust implementation of Move's `native public fun from_bytes<T: copy + drop>(vector<u8>): T`
+///
+/// Only types with `copy` and `drop` can be created from bytes, so resources and signers
+/// can't be forged.
+pub fn native_from_bytes(
+    context: &mut NativeContext,
+    mut ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 1);
+
+    let bytes = pop_arg!(args, Vec<u8>);
+    let arg_type = ty_args.pop().unwrap();
+
+    // cost is proportional to the size of the serialized value
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::BCS_FROM_BYTES,
+        bytes.len(),
+    );
+
+    let abilities = context.abilities(&arg_type)?;
+    if !abilities.has_copy() || !abilities.has_drop() {
+        return Ok(NativeResult::err(cost, INVALID_TYPE_PARAM));
+    }
+
+    let layout = match context.type_to_type_layout(&arg_type)? {
+        Some(layout) => layout,
+        None => return Ok(NativeResult::err(cost, INVALID_TYPE_PARAM)),
+    };
+
+    match Value::simple_deserialize(&bytes, &layout) {
+        Some(value) => Ok(NativeResult::ok(cost, smallvec![value])),
+        None => Ok(NativeResult::err(cost, NFE_BCS_DESERIALIZATION_FAILURE)),
+    }
+}
@@ -2,20 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use alloc::collections::VecDeque;
+use alloc::string::String;
 use alloc::vec::Vec;
 use move_binary_format::errors::PartialVMResult;
 use move_core_types::gas_schedule::ONE_GAS_UNIT;
 use move_vm_runtime::native_functions::NativeContext;
-#[allow(unused_imports)]
 use move_vm_types::values::{values_impl::debug::print_reference, Reference};
-#[allow(unused_imports)]
 use move_vm_types::{
     loaded_data::runtime_types::Type, natives::function::NativeResult, pop_arg, values::Value,
 };
 use smallvec::smallvec;
 
-#[allow(unused_mut)]
-#[allow(unused_variables)]
 pub fn native_print(
     context: &mut NativeContext,
     mut ty_args: Vec<Type>,
@@ -24,21 +21,21 @@ pub fn native_print(
     debug_assert!(ty_args.len() == 1);
     debug_assert!(args.len() == 1);
 
-    // No-op if the feature flag is not present.
-    #[cfg(feature = "testing")]
-    {
-        let ty = ty_args.pop().unwrap();
-        let r = pop_arg!(args, Reference);
+    let ty = ty_args.pop().unwrap();
+    let r = pop_arg!(args, Reference);
 
+    // The value is formatted only if someone receives it, the cost does not depend on it.
+    if cfg!(feature = "testing") || context.has_debug_sink() {
         let mut buf = String::new();
         print_reference(&mut buf, &r)?;
+        #[cfg(feature = "testing")]
         println!("[debug] {}", buf);
+        context.debug_print(&ty, &buf)?;
     }
 
     Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]))
 }
 
-#[allow(unused_variables)]
 pub fn native_print_stack_trace(
     context: &mut NativeContext,
     ty_args: Vec<Type>,
@@ -53,6 +50,7 @@ pub fn native_print_stack_trace(
         context.print_stack_trace(&mut s)?;
         println!("{}", s);
     }
+    context.debug_print_stack_trace()?;
 
     Ok(NativeResult::ok(ONE_GAS_UNIT, smallvec![]))
 }
@@ -0,0 +1,48 @@
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use move_binary_format::errors::PartialVMResult;
+use move_core_types::vm_status::sub_status::NFE_HOST_QUERY_UNSUPPORTED;
+use move_vm_runtime::native_functions::NativeContext;
+use move_vm_types::{
+    gas_schedule::NativeCostIndex,
+    loaded_data::runtime_types::Type,
+    natives::function::{native_gas, NativeResult},
+    pop_arg,
+    values::Value,
+};
+use smallvec::smallvec;
+
+/// Queries the host environment by the key.
+/// Gas is charged by the total size of the key and the response.
+pub fn native_query(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let key = pop_arg!(arguments, Vec<u8>);
+
+    match context.host_query(&key) {
+        Some(Ok(response)) => {
+            let cost = native_gas(
+                context.cost_table(),
+                NativeCostIndex::HOST_QUERY,
+                key.len() + response.len(),
+            );
+            Ok(NativeResult::ok(
+                cost,
+                smallvec![Value::vector_u8(response)],
+            ))
+        }
+        Some(Err(code)) => {
+            let cost = native_gas(context.cost_table(), NativeCostIndex::HOST_QUERY, key.len());
+            Ok(NativeResult::err(cost, code))
+        }
+        None => {
+            let cost = native_gas(context.cost_table(), NativeCostIndex::HOST_QUERY, key.len());
+            Ok(NativeResult::err(cost, NFE_HOST_QUERY_UNSUPPORTED))
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub mod account;
 pub mod bcs;
 pub mod event;
 pub mod hash;
+pub mod host;
 pub mod reflect;
 pub mod signature;
 pub mod signer;
@@ -22,9 +23,12 @@ use move_vm_runtime::native_functions::{NativeFunction, NativeFunctionTable};
 pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
     const NATIVES: &[(&str, &str, NativeFunction)] = &[
         ("BCS", "to_bytes", bcs::native_to_bytes),
+        ("BCS", "from_bytes", bcs::native_from_bytes),
         ("Event", "write_to_event_store", event::write_to_event_store),
         ("Hash", "sha2_256", hash::native_sha2_256),
         ("Hash", "sha3_256", hash::native_sha3_256),
+        ("Hash", "keccak256", hash::native_keccak256),
+        ("Hash", "blake2b_256", hash::native_blake2b_256),
         ("Signer", "borrow_address", signer::native_borrow_address),
         ("Vector", "length", vector::native_length),
         ("Vector", "empty", vector::native_empty),
@@ -34,6 +38,11 @@ pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
         ("Vector", "pop_back", vector::native_pop),
         ("Vector", "destroy_empty", vector::native_destroy_empty),
         ("Vector", "swap", vector::native_swap),
+        ("Vector", "append", vector::native_append),
+        ("Vector", "reverse", vector::native_reverse),
+        ("Vector", "contains", vector::native_contains),
+        ("Vector", "index_of", vector::native_index_of),
+        ("Vector", "remove", vector::native_remove),
         ("Debug", "print", debug::native_print),
         (
             "Debug",
@@ -56,6 +65,22 @@ pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
         ("U256", "sub", u256::sub),
         ("U256", "mul", u256::mul),
         ("U256", "div", u256::div),
+        ("U256", "mod", u256::rem),
+        ("U256", "pow", u256::pow),
+        ("U256", "mul_div", u256::mul_div),
+        ("U256", "shl", u256::shl),
+        ("U256", "shr", u256::shr),
+        ("U256", "and", u256::and),
+        ("U256", "or", u256::or),
+        ("U256", "xor", u256::xor),
+        ("U256", "lt", u256::lt),
+        ("U256", "le", u256::le),
+        ("U256", "gt", u256::gt),
+        ("U256", "ge", u256::ge),
+        ("U256", "from_le_bytes", u256::from_le_bytes),
+        ("U256", "from_be_bytes", u256::from_be_bytes),
+        ("U256", "to_le_bytes", u256::to_le_bytes),
+        ("U256", "to_be_bytes", u256::to_be_bytes),
         (
             "PontAccount",
             "create_signer",
@@ -76,7 +101,25 @@ pub fn all_natives(move_std_addr: AccountAddress) -> NativeFunctionTable {
             "ed25519_verify",
             signature::native_ed25519_signature_verification,
         ),
+        (
+            "Signature",
+            "ed25519_threshold_verify",
+            signature::native_ed25519_threshold_verification,
+        ),
+        (
+            "Signature",
+            "secp256k1_recover",
+            signature::native_secp256k1_recover,
+        ),
+        (
+            "Signature",
+            "secp256k1_verify",
+            signature::native_secp256k1_verify,
+        ),
         ("Reflect", "type_info", reflect::type_info),
+        ("Reflect", "type_name", reflect::type_name),
+        ("Reflect", "is_same_type", reflect::is_same_type),
+        ("Host", "query", host::native_query),
     ];
     NATIVES
         .iter()
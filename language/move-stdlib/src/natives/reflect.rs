@@ -1,4 +1,5 @@
 use alloc::collections::VecDeque;
+use alloc::string::ToString;
 use alloc::vec;
 use alloc::vec::Vec;
 use move_binary_format::errors::PartialVMResult;
@@ -36,3 +37,37 @@ pub fn type_info(
         Ok(NativeResult::err(cost, INVALID_TYPE_PARAM))
     }
 }
+
+/// Returns the canonical name of any type in the `TypeTag` display format,
+/// e.g. `vector<0x1::Coins::Coin<0x1::PONT::T>>`.
+pub fn type_name(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(arguments.is_empty());
+
+    let name = context.type_to_type_tag(&ty_args[0])?.to_string();
+    let cost = native_gas(context.cost_table(), NativeCostIndex::TYPE_NAME, name.len());
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::vector_u8(name.into_bytes())],
+    ))
+}
+
+/// Checks whether two type arguments are the same type.
+pub fn is_same_type(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 2);
+    debug_assert!(arguments.is_empty());
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::TYPE_EQ, 0);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(ty_args[0] == ty_args[1])],
+    ))
+}
@@ -4,7 +4,7 @@
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 use core::convert::TryFrom;
-use diem_crypto::{ed25519, traits::*};
+use diem_crypto::{ed25519, multi_ed25519, traits::*};
 use move_binary_format::errors::PartialVMResult;
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
@@ -74,3 +74,203 @@ pub fn native_ed25519_signature_verification(
         smallvec![Value::bool(verify_result)],
     ))
 }
+
+pub fn native_ed25519_threshold_verification(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    // Every signature of the bitmap is verified separately.
+    let num_of_sigs = core::cmp::max(1, signature.len() / ed25519::ED25519_SIGNATURE_LENGTH);
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::ED25519_THRESHOLD_VERIFY,
+        msg.len() * num_of_sigs,
+    );
+
+    let sig = match multi_ed25519::MultiEd25519Signature::try_from(signature.as_slice()) {
+        Ok(sig) => sig,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+    let pk = match multi_ed25519::MultiEd25519PublicKey::try_from(pubkey.as_slice()) {
+        Ok(pk) => pk,
+        Err(_) => {
+            return Ok(NativeResult::ok(cost, smallvec![Value::bool(false)]));
+        }
+    };
+
+    let verify_result = sig.verify_arbitrary_msg(msg.as_slice(), &pk).is_ok();
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+pub fn native_secp256k1_recover(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let msg_hash = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::SECP256K1_RECOVER,
+        msg_hash.len(),
+    );
+
+    match secp256k1_recover(&signature, &msg_hash) {
+        Some(pubkey) => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::vector_u8(pubkey), Value::bool(true)],
+        )),
+        None => Ok(NativeResult::ok(
+            cost,
+            smallvec![Value::vector_u8(Vec::new()), Value::bool(false)],
+        )),
+    }
+}
+
+pub fn native_secp256k1_verify(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(_ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let msg_hash = pop_arg!(arguments, Vec<u8>);
+    let pubkey = pop_arg!(arguments, Vec<u8>);
+    let signature = pop_arg!(arguments, Vec<u8>);
+
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::SECP256K1_VERIFY,
+        msg_hash.len(),
+    );
+
+    let verify_result = secp256k1_verify(&signature, &pubkey, &msg_hash);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(verify_result)],
+    ))
+}
+
+/// Recovers the 64-byte uncompressed public key (without the `0x04` prefix) from the 65-byte
+/// `r || s || v` signature of the 32-byte message hash.
+/// Both `0/1` and Ethereum `27/28` recovery ids are accepted.
+fn secp256k1_recover(signature: &[u8], msg_hash: &[u8]) -> Option<Vec<u8>> {
+    if signature.len() != 65 {
+        return None;
+    }
+    let message = secp256k1_message(msg_hash)?;
+    let rs = libsecp256k1::Signature::parse_standard_slice(&signature[..64]).ok()?;
+    let v = match signature[64] {
+        v @ 27..=28 => v - 27,
+        v => v,
+    };
+    let v = libsecp256k1::RecoveryId::parse(v).ok()?;
+    let pubkey = libsecp256k1::recover(&message, &rs, &v).ok()?;
+    Some(pubkey.serialize()[1..].to_vec())
+}
+
+/// Verifies the 64-byte `r || s` (or 65-byte `r || s || v`) signature of the 32-byte message hash.
+/// The public key may be compressed (33 bytes), uncompressed (65 bytes) or raw (64 bytes).
+fn secp256k1_verify(signature: &[u8], pubkey: &[u8], msg_hash: &[u8]) -> bool {
+    let message = match secp256k1_message(msg_hash) {
+        Some(message) => message,
+        None => return false,
+    };
+    if signature.len() != 64 && signature.len() != 65 {
+        return false;
+    }
+    let sig = match libsecp256k1::Signature::parse_standard_slice(&signature[..64]) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    let pk = match libsecp256k1::PublicKey::parse_slice(pubkey, None) {
+        Ok(pk) => pk,
+        Err(_) => return false,
+    };
+    libsecp256k1::verify(&message, &sig, &pk)
+}
+
+fn secp256k1_message(msg_hash: &[u8]) -> Option<libsecp256k1::Message> {
+    <[u8; 32]>::try_from(msg_hash)
+        .ok()
+        .map(|hash| libsecp256k1::Message::parse(&hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Test vector of the Ethereum `ecrecover` precompile.
+    // The signer address is `0x7156526fbd7a3c72969b54f64e42c10fbb768c8a`.
+    const HASH: &str = "456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3";
+    const SIGNATURE: &str = "9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608\
+                             4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada1c";
+    const PUBKEY: &str = "f57c1d4c961024e998eaec4b6bebec90e788ef5ade22e636ce76111b60db107d\
+                          4c3404b9908a2f357c84ccb48cf412be41d09574a9291c9c7eb5173ccf2a339f";
+
+    fn decode(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_secp256k1_recover() {
+        let hash = decode(HASH);
+        let mut signature = decode(SIGNATURE);
+        assert_eq!(secp256k1_recover(&signature, &hash), Some(decode(PUBKEY)));
+
+        signature[64] = 1;
+        assert_eq!(secp256k1_recover(&signature, &hash), Some(decode(PUBKEY)));
+
+        signature[64] = 0;
+        assert_ne!(secp256k1_recover(&signature, &hash), Some(decode(PUBKEY)));
+
+        signature[64] = 4;
+        assert_eq!(secp256k1_recover(&signature, &hash), None);
+
+        assert_eq!(secp256k1_recover(&signature[..64], &hash), None);
+        assert_eq!(secp256k1_recover(&decode(SIGNATURE), &hash[..31]), None);
+    }
+
+    #[test]
+    fn test_secp256k1_verify() {
+        let hash = decode(HASH);
+        let signature = decode(SIGNATURE);
+        let mut pubkey = vec![0x04];
+        pubkey.extend(decode(PUBKEY));
+
+        assert!(secp256k1_verify(&signature, &pubkey, &hash));
+        assert!(secp256k1_verify(&signature[..64], &pubkey, &hash));
+        assert!(secp256k1_verify(&signature, &pubkey[1..], &hash));
+
+        let mut compressed = vec![0x03];
+        compressed.extend(&pubkey[1..33]);
+        assert!(secp256k1_verify(&signature, &compressed, &hash));
+
+        let mut other_hash = hash.clone();
+        other_hash[0] ^= 1;
+        assert!(!secp256k1_verify(&signature, &pubkey, &other_hash));
+        assert!(!secp256k1_verify(&signature[..63], &pubkey, &hash));
+        assert!(!secp256k1_verify(&signature, &pubkey[..33], &hash));
+    }
+}
@@ -1,11 +1,12 @@
 use alloc::borrow::ToOwned;
 use alloc::collections::VecDeque;
 use alloc::format;
+use alloc::string::String;
 use alloc::vec;
 use alloc::vec::Vec;
-use core::ops::Div;
+use core::ops::{BitAnd, BitOr, BitXor, Div, Rem, Shl, Shr};
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
-use move_core_types::vm_status::StatusCode;
+use move_core_types::vm_status::{sub_status, StatusCode};
 use move_vm_runtime::native_functions::NativeContext;
 use move_vm_types::{
     gas_schedule::NativeCostIndex,
@@ -21,6 +22,11 @@ construct_uint! {
     pub struct U256(4);
 }
 
+construct_uint! {
+    /// Intermediate type of `mul_div`.
+    struct U512(8);
+}
+
 pub fn from_u8(
     context: &mut NativeContext,
     ty_args: Vec<Type>,
@@ -204,6 +210,247 @@ pub fn add(
     Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
 }
 
+pub fn rem(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_rem(l, r)?;
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_MOD, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+pub fn pow(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let exp = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let base = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_pow(base, exp)?;
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_POW, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+/// Returns `a * b / c` without overflowing on the intermediate product.
+pub fn mul_div(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 3);
+
+    let c = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let b = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let a = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let res = checked_mul_div(a, b, c)?;
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_MUL_DIV, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(res)]))
+}
+
+pub fn shl(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let shift = pop_arg!(arguments, u8);
+    let val = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_SHL, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(val.shl(shift))]))
+}
+
+pub fn shr(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let shift = pop_arg!(arguments, u8);
+    let val = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_SHR, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(val.shr(shift))]))
+}
+
+pub fn and(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_AND, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l.bitand(r))]))
+}
+
+pub fn or(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_OR, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l.bitor(r))]))
+}
+
+pub fn xor(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_XOR, 0);
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(l.bitxor(r))]))
+}
+
+pub fn lt(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    compare(context, ty_args, arguments, |l, r| l < r)
+}
+
+pub fn le(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    compare(context, ty_args, arguments, |l, r| l <= r)
+}
+
+pub fn gt(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    compare(context, ty_args, arguments, |l, r| l > r)
+}
+
+pub fn ge(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    compare(context, ty_args, arguments, |l, r| l >= r)
+}
+
+fn compare(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+    cmp: fn(&U256, &U256) -> bool,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 2);
+
+    let r = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let l = unwrap_u256(pop_arg!(arguments, Struct))?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_CMP, 0);
+    Ok(NativeResult::ok(cost, smallvec![Value::bool(cmp(&l, &r))]))
+}
+
+pub fn from_le_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::U256_FROM_BYTES,
+        bytes.len(),
+    );
+    let u256 = from_bytes(&bytes, U256::from_little_endian)?;
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(u256)]))
+}
+
+pub fn from_be_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let bytes = pop_arg!(arguments, Vec<u8>);
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::U256_FROM_BYTES,
+        bytes.len(),
+    );
+    let u256 = from_bytes(&bytes, U256::from_big_endian)?;
+    Ok(NativeResult::ok(cost, smallvec![wrap_u256(u256)]))
+}
+
+pub fn to_le_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let u256 = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let mut bytes = vec![0; 32];
+    u256.to_little_endian(&mut bytes);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_TO_BYTES, 32);
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(bytes)]))
+}
+
+pub fn to_be_bytes(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut arguments: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.is_empty());
+    debug_assert!(arguments.len() == 1);
+
+    let u256 = unwrap_u256(pop_arg!(arguments, Struct))?;
+    let mut bytes = vec![0; 32];
+    u256.to_big_endian(&mut bytes);
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::U256_TO_BYTES, 32);
+    Ok(NativeResult::ok(cost, smallvec![Value::vector_u8(bytes)]))
+}
+
 pub fn unwrap_u256(u256: Struct) -> PartialVMResult<U256> {
     u256.unpack()?
         .next()
@@ -229,3 +476,228 @@ fn wrap_u256(val: U256) -> Value {
     val.to_little_endian(&mut bytes);
     Value::struct_(Struct::pack(vec![Value::vector_u8(bytes)]))
 }
+
+/// Decodes up to 32 bytes with the `decode` function.
+fn from_bytes(bytes: &[u8], decode: fn(&[u8]) -> U256) -> PartialVMResult<U256> {
+    if bytes.len() > 32 {
+        return Err(arithmetic_error(
+            sub_status::NFE_U256_BYTES_LENGTH,
+            format!("Cannot cast {} bytes to u256", bytes.len()),
+        ));
+    }
+    Ok(decode(bytes))
+}
+
+fn checked_rem(l: U256, r: U256) -> PartialVMResult<U256> {
+    if r.is_zero() {
+        return Err(arithmetic_error(
+            sub_status::NFE_U256_MOD_BY_ZERO,
+            format!("Cannot mod {:?} by {:?}", l, r),
+        ));
+    }
+    Ok(l.rem(r))
+}
+
+fn checked_pow(base: U256, exp: U256) -> PartialVMResult<U256> {
+    base.checked_pow(exp).ok_or_else(|| {
+        arithmetic_error(
+            sub_status::NFE_U256_POW_OVERFLOW,
+            format!("Cannot pow {:?} to {:?}", base, exp),
+        )
+    })
+}
+
+/// Computes `a * b / c` in 512 bits.
+fn checked_mul_div(a: U256, b: U256, c: U256) -> PartialVMResult<U256> {
+    if c.is_zero() {
+        return Err(arithmetic_error(
+            sub_status::NFE_U256_MUL_DIV_BY_ZERO,
+            format!("Cannot mul {:?} and {:?} and div by {:?}", a, b, c),
+        ));
+    }
+
+    // The product of two 256-bit numbers always fits into 512 bits.
+    let res = (to_u512(a) * to_u512(b)).div(to_u512(c));
+    if res > to_u512(U256::MAX) {
+        return Err(arithmetic_error(
+            sub_status::NFE_U256_MUL_DIV_OVERFLOW,
+            format!("Cannot mul {:?} and {:?} and div by {:?}", a, b, c),
+        ));
+    }
+
+    let mut bytes = [0; 64];
+    res.to_little_endian(&mut bytes);
+    Ok(U256::from_little_endian(&bytes[..32]))
+}
+
+fn arithmetic_error(sub_status: u64, message: String) -> PartialVMError {
+    PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
+        .with_sub_status(sub_status)
+        .with_message(message)
+}
+
+fn to_u512(val: U256) -> U512 {
+    let mut bytes = [0; 32];
+    val.to_little_endian(&mut bytes);
+    U512::from_little_endian(&bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cmp::Ordering;
+    use move_binary_format::errors::Location;
+
+    fn u256(value: u128) -> U256 {
+        U256::from(value)
+    }
+
+    fn round_trip(value: U256) -> U256 {
+        unwrap_u256(wrap_u256(value).value_as::<Struct>().unwrap()).unwrap()
+    }
+
+    fn failure(res: PartialVMResult<U256>) -> (StatusCode, Option<u64>) {
+        let err = res.unwrap_err().finish(Location::Undefined);
+        (err.major_status(), err.sub_status())
+    }
+
+    fn arithmetic_failure(sub_status: u64) -> (StatusCode, Option<u64>) {
+        (StatusCode::ARITHMETIC_ERROR, Some(sub_status))
+    }
+
+    #[test]
+    fn test_mod() {
+        assert_eq!(checked_rem(u256(10), u256(3)).unwrap(), u256(1));
+        assert_eq!(
+            checked_rem(U256::MAX, u256(1) << 128).unwrap(),
+            u256(u128::MAX)
+        );
+        assert_eq!(
+            failure(checked_rem(u256(1), u256(0))),
+            arithmetic_failure(sub_status::NFE_U256_MOD_BY_ZERO)
+        );
+    }
+
+    #[test]
+    fn test_mul_div() {
+        assert_eq!(
+            checked_mul_div(u256(6), u256(7), u256(4)).unwrap(),
+            u256(10)
+        );
+        assert_eq!(
+            checked_mul_div(u256(0), U256::MAX, u256(1)).unwrap(),
+            u256(0)
+        );
+        // The intermediate product does not fit into 256 bits.
+        assert_eq!(
+            checked_mul_div(U256::MAX, U256::MAX, U256::MAX).unwrap(),
+            U256::MAX
+        );
+        assert_eq!(
+            checked_mul_div(U256::MAX, u256(3), u256(6)).unwrap(),
+            U256::MAX / 2
+        );
+        assert_eq!(
+            checked_mul_div(U256::MAX, u256(2), u256(3)).unwrap(),
+            U256::MAX / 3 * 2
+        );
+        assert_eq!(
+            failure(checked_mul_div(U256::MAX, u256(2), u256(1))),
+            arithmetic_failure(sub_status::NFE_U256_MUL_DIV_OVERFLOW)
+        );
+        assert_eq!(
+            failure(checked_mul_div(U256::MAX, u256(3), u256(2))),
+            arithmetic_failure(sub_status::NFE_U256_MUL_DIV_OVERFLOW)
+        );
+        assert_eq!(
+            failure(checked_mul_div(u256(1), u256(1), u256(0))),
+            arithmetic_failure(sub_status::NFE_U256_MUL_DIV_BY_ZERO)
+        );
+    }
+
+    #[test]
+    fn test_pow() {
+        assert_eq!(checked_pow(u256(2), u256(10)).unwrap(), u256(1024));
+        assert_eq!(checked_pow(u256(0), u256(0)).unwrap(), u256(1));
+        assert_eq!(checked_pow(u256(2), u256(255)).unwrap(), u256(1) << 255);
+        assert_eq!(
+            failure(checked_pow(u256(2), u256(256))),
+            arithmetic_failure(sub_status::NFE_U256_POW_OVERFLOW)
+        );
+        assert_eq!(
+            failure(checked_pow(U256::MAX, u256(2))),
+            arithmetic_failure(sub_status::NFE_U256_POW_OVERFLOW)
+        );
+    }
+
+    #[test]
+    fn test_shifts() {
+        assert_eq!(u256(1).shl(255u8), U256::MAX - (U256::MAX >> 1));
+        assert_eq!(u256(3).shl(255u8), u256(1) << 255);
+        assert_eq!(U256::MAX.shr(255u8), u256(1));
+        assert_eq!(u256(0x100).shr(8u8), u256(1));
+        assert_eq!(u256(1).shr(1u8), u256(0));
+        assert_eq!(u256(u128::MAX).shl(128u8).shr(128u8), u256(u128::MAX));
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let high = U256::MAX << 128;
+        let low = u256(u128::MAX);
+        assert_eq!(high.bitand(low), u256(0));
+        assert_eq!(high.bitor(low), U256::MAX);
+        assert_eq!(U256::MAX.bitxor(low), high);
+        assert_eq!(u256(0b1100).bitxor(u256(0b1010)), u256(0b0110));
+    }
+
+    #[test]
+    fn test_comparisons() {
+        let cases = [
+            (u256(1), u256(2)),
+            (u256(u128::MAX), u256(1) << 128),
+            (u256(0), U256::MAX),
+        ];
+        for &(small, big) in &cases {
+            assert!(small < big && small <= big);
+            assert!(big > small && big >= small);
+            assert_eq!(small.cmp(&small), Ordering::Equal);
+            assert_eq!(big.cmp(&small), Ordering::Greater);
+        }
+    }
+
+    #[test]
+    fn test_byte_conversions() {
+        let mut le = vec![0; 32];
+        le[0] = 1;
+        le[31] = 0x80;
+        let value = from_bytes(&le, U256::from_little_endian).unwrap();
+        assert_eq!(value, (u256(1) << 255) + 1);
+        let mut be = le.clone();
+        be.reverse();
+        assert_eq!(from_bytes(&be, U256::from_big_endian).unwrap(), value);
+
+        let mut bytes = vec![0; 32];
+        value.to_little_endian(&mut bytes);
+        assert_eq!(bytes, le);
+        value.to_big_endian(&mut bytes);
+        assert_eq!(bytes, be);
+
+        assert_eq!(
+            from_bytes(&[1, 2], U256::from_little_endian).unwrap(),
+            u256(0x0201)
+        );
+        assert_eq!(
+            from_bytes(&[1, 2], U256::from_big_endian).unwrap(),
+            u256(0x0102)
+        );
+        assert_eq!(from_bytes(&[], U256::from_big_endian).unwrap(), u256(0));
+        assert_eq!(
+            failure(from_bytes(&[0; 33], U256::from_little_endian)),
+            arithmetic_failure(sub_status::NFE_U256_BYTES_LENGTH)
+        );
+
+        for &value in &[u256(0), u256(u128::MAX), U256::MAX] {
+            assert_eq!(round_trip(value), value);
+        }
+    }
+}
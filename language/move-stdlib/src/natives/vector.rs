@@ -9,12 +9,16 @@ use move_vm_types::{
     loaded_data::runtime_types::Type,
     natives::function::{native_gas, NativeResult},
     pop_arg,
-    values::{Value, Vector, VectorRef},
+    values::{Reference, Value, Vector, VectorRef},
 };
+use smallvec::smallvec;
 
 use alloc::collections::VecDeque;
 use alloc::vec::Vec;
 
+/// Abort code of `Vector::remove` of the Move stdlib.
+const EINDEX_OUT_OF_BOUNDS: u64 = 0;
+
 pub fn native_empty(
     context: &mut NativeContext,
     ty_args: Vec<Type>,
@@ -112,3 +116,112 @@ pub fn native_swap(
     let cost = native_gas(context.cost_table(), NativeCostIndex::SWAP, 1);
     NativeResult::map_partial_vm_result_empty(cost, r.swap(idx1, idx2, &ty_args[0]))
 }
+
+pub fn native_append(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let other = pop_arg!(args, Vector);
+    let r = pop_arg!(args, VectorRef);
+    let len = vector_len(&r, &ty_args[0])?;
+    r.append(other, &ty_args[0])?;
+    let appended = vector_len(&r, &ty_args[0])? - len;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::APPEND, appended);
+    Ok(NativeResult::ok(cost, smallvec![]))
+}
+
+pub fn native_reverse(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 1);
+
+    let r = pop_arg!(args, VectorRef);
+    let len = vector_len(&r, &ty_args[0])?;
+    let cost = native_gas(context.cost_table(), NativeCostIndex::REVERSE, len);
+    NativeResult::map_partial_vm_result_empty(cost, r.reverse(&ty_args[0]))
+}
+
+pub fn native_contains(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let e = pop_arg!(args, Reference).read_ref()?;
+    let r = pop_arg!(args, VectorRef);
+    let (idx, compared) = index_of(&r, e, &ty_args[0])?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::CONTAINS, compared);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![Value::bool(idx.is_some())],
+    ))
+}
+
+pub fn native_index_of(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let e = pop_arg!(args, Reference).read_ref()?;
+    let r = pop_arg!(args, VectorRef);
+    let (idx, compared) = index_of(&r, e, &ty_args[0])?;
+
+    let cost = native_gas(context.cost_table(), NativeCostIndex::INDEX_OF, compared);
+    Ok(NativeResult::ok(
+        cost,
+        smallvec![
+            Value::bool(idx.is_some()),
+            Value::u64(idx.unwrap_or_default() as u64)
+        ],
+    ))
+}
+
+pub fn native_remove(
+    context: &mut NativeContext,
+    ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    debug_assert!(ty_args.len() == 1);
+    debug_assert!(args.len() == 2);
+
+    let idx = pop_arg!(args, u64) as usize;
+    let r = pop_arg!(args, VectorRef);
+    let len = vector_len(&r, &ty_args[0])?;
+    let cost = native_gas(
+        context.cost_table(),
+        NativeCostIndex::REMOVE,
+        len.saturating_sub(idx),
+    );
+    if idx >= len {
+        return Ok(NativeResult::err(cost, EINDEX_OUT_OF_BOUNDS));
+    }
+    NativeResult::map_partial_vm_result_one(cost, r.remove(idx, &ty_args[0]))
+}
+
+fn vector_len(r: &VectorRef, type_param: &Type) -> PartialVMResult<usize> {
+    Ok(r.len(type_param)?.value_as::<u64>()? as usize)
+}
+
+/// Returns the index of the first element equal to `e` and the number of the compared elements.
+fn index_of(r: &VectorRef, e: Value, type_param: &Type) -> PartialVMResult<(Option<usize>, usize)> {
+    let idx = r.index_of(e, type_param)?;
+    let compared = match idx {
+        Some(idx) => idx + 1,
+        None => vector_len(r, type_param)?,
+    };
+    Ok((idx, compared))
+}
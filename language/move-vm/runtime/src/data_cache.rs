@@ -189,10 +189,7 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
                 Ok(None) => GlobalValue::none(),
                 Err(err) => {
                     let msg = format!("Unexpected storage error: {:?}", err);
-                    return Err(
-                        PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                            .with_message(msg),
-                    );
+                    return Err(PartialVMError::new(StatusCode::STORAGE_ERROR).with_message(msg));
                 }
             };
 
@@ -219,11 +216,9 @@ impl<'r, 'l, S: MoveResolver> DataStore for TransactionDataCache<'r, 'l, S> {
                 .finish(Location::Undefined)),
             Err(err) => {
                 let msg = format!("Unexpected storage error: {:?}", err);
-                Err(
-                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                        .with_message(msg)
-                        .finish(Location::Undefined),
-                )
+                Err(PartialVMError::new(StatusCode::STORAGE_ERROR)
+                    .with_message(msg)
+                    .finish(Location::Undefined))
             }
         }
     }
@@ -67,6 +67,8 @@ pub(crate) struct Interpreter {
     operand_stack: Stack,
     /// The stack of active functions.
     call_stack: CallStack,
+    /// Whether the errors carry a snapshot of the call stack.
+    exec_state: bool,
 }
 
 impl Interpreter {
@@ -82,16 +84,17 @@ impl Interpreter {
     ) -> VMResult<Vec<Value>> {
         // We count the intrinsic cost of the transaction here, since that needs to also cover the
         // setup of the function.
-        let mut interp = Self::new();
+        let mut interp = Self::new(loader.exec_state());
         interp.execute(loader, data_store, gas_status, function, ty_args, args)
     }
 
     /// Create a new instance of an `Interpreter` in the context of a transaction with a
     /// given module cache and gas schedule.
-    fn new() -> Self {
+    fn new(exec_state: bool) -> Self {
         Interpreter {
             operand_stack: Stack::new(),
             call_stack: CallStack::new(),
+            exec_state,
         }
     }
 
@@ -161,7 +164,14 @@ impl Interpreter {
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
                     if func.is_native() {
-                        self.call_native(&resolver, data_store, gas_status, func, vec![])?;
+                        self.call_native(
+                            &resolver,
+                            data_store,
+                            gas_status,
+                            &current_frame.function,
+                            func,
+                            vec![],
+                        )?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
@@ -194,7 +204,14 @@ impl Interpreter {
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
                     if func.is_native() {
-                        self.call_native(&resolver, data_store, gas_status, func, ty_args)?;
+                        self.call_native(
+                            &resolver,
+                            data_store,
+                            gas_status,
+                            &current_frame.function,
+                            func,
+                            ty_args,
+                        )?;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
@@ -237,23 +254,29 @@ impl Interpreter {
         resolver: &Resolver,
         data_store: &mut dyn DataStore,
         gas_status: &mut GasStatus,
+        caller: &Function,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> VMResult<()> {
         // Note: refactor if native functions push a frame on the stack
-        self.call_native_impl(resolver, data_store, gas_status, function.clone(), ty_args)
-            .map_err(|e| match function.module_id() {
-                Some(id) => e
-                    .at_code_offset(function.index(), 0)
-                    .finish(Location::Module(id.clone())),
-                None => {
-                    let err = PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
-                        .with_message(
-                            "Unexpected native function not located in a module".to_owned(),
-                        );
-                    self.set_location(err)
-                }
-            })
+        self.call_native_impl(
+            resolver,
+            data_store,
+            gas_status,
+            caller,
+            function.clone(),
+            ty_args,
+        )
+        .map_err(|e| match function.module_id() {
+            Some(id) => e
+                .at_code_offset(function.index(), 0)
+                .finish(Location::Module(id.clone())),
+            None => {
+                let err = PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                    .with_message("Unexpected native function not located in a module".to_owned());
+                self.set_location(err)
+            }
+        })
     }
 
     fn call_native_impl(
@@ -261,6 +284,7 @@ impl Interpreter {
         resolver: &Resolver,
         data_store: &mut dyn DataStore,
         gas_status: &mut GasStatus,
+        caller: &Function,
         function: Arc<Function>,
         ty_args: Vec<Type>,
     ) -> PartialVMResult<()> {
@@ -269,7 +293,7 @@ impl Interpreter {
         for _ in 0..expected_args {
             arguments.push_front(self.operand_stack.pop()?);
         }
-        let mut native_context = NativeContext::new(self, data_store, gas_status, resolver);
+        let mut native_context = NativeContext::new(self, data_store, gas_status, resolver, caller);
         let native_function = function.get_native()?;
         let result = native_function(&mut native_context, ty_args, arguments)?;
         gas_status.deduct_gas(result.cost)?;
@@ -393,6 +417,7 @@ impl Interpreter {
     //
 
     /// Given an `VMStatus` generate a core dump if the error is an `InvariantViolation`.
+    /// Attaches a snapshot of the call stack if enabled.
     fn maybe_core_dump(&self, mut err: VMError, current_frame: &Frame) -> VMError {
         // a verification error cannot happen at runtime so change it into an invariant violation.
         if err.status_type() == StatusType::Verification {
@@ -412,9 +437,30 @@ impl Interpreter {
                 err, state,
             );
         }
+        if self.exec_state && err.exec_state().is_none() {
+            err = err.with_exec_state(self.get_exec_state(current_frame));
+        }
         err
     }
 
+    /// Returns the call stack along with the current frame.
+    fn get_exec_state(&self, current_frame: &Frame) -> ExecutionState {
+        let stack_trace = self
+            .call_stack
+            .0
+            .iter()
+            .chain(core::iter::once(current_frame))
+            .map(|frame| {
+                (
+                    frame.function.module_id().cloned(),
+                    frame.function.index(),
+                    frame.pc,
+                )
+            })
+            .collect();
+        ExecutionState::new(stack_trace)
+    }
+
     #[allow(dead_code)]
     fn debug_print_frame(
         &self,
@@ -3,7 +3,7 @@
 
 use crate::{
     logging::expect_no_verification_errors,
-    native_functions::{NativeFunction, NativeFunctions},
+    native_functions::{DebugSink, NativeFunction, NativeFunctions, NativeHost},
 };
 use alloc::borrow::ToOwned;
 use alloc::boxed::Box;
@@ -13,7 +13,7 @@ use alloc::string::ToString;
 use alloc::sync::Arc;
 use alloc::vec::Vec;
 use bytecode_verifier::{self, cyclic_dependencies, dependencies, script_signature};
-use core::cell::RefCell;
+use core::cell::{Cell, RefCell};
 use core::{fmt::Debug, hash::Hash};
 use hashbrown::HashMap;
 use move_binary_format::{
@@ -430,18 +430,48 @@ pub struct Loader {
     module_cache: RefCell<ModuleCache>,
     type_cache: RefCell<TypeCache>,
     natives: NativeFunctions,
+    host: Option<Box<dyn NativeHost>>,
+    debug_sink: Option<Box<dyn DebugSink>>,
+    exec_state: Cell<bool>,
 }
 
 impl Loader {
-    pub fn new(natives: NativeFunctions) -> Self {
+    pub fn new(
+        natives: NativeFunctions,
+        host: Option<Box<dyn NativeHost>>,
+        debug_sink: Option<Box<dyn DebugSink>>,
+    ) -> Self {
         Self {
             scripts: RefCell::new(ScriptCache::new()),
             module_cache: RefCell::new(ModuleCache::new()),
             type_cache: RefCell::new(TypeCache::new()),
             natives,
+            host,
+            debug_sink,
+            exec_state: Cell::new(false),
         }
     }
 
+    /// Returns the host environment available to the natives.
+    pub(crate) fn host(&self) -> Option<&dyn NativeHost> {
+        self.host.as_deref()
+    }
+
+    /// Returns the receiver of the `Debug::print` output.
+    pub(crate) fn debug_sink(&self) -> Option<&dyn DebugSink> {
+        self.debug_sink.as_deref()
+    }
+
+    /// Enables the call stack snapshots attached to the execution errors.
+    pub fn set_exec_state(&self, enabled: bool) {
+        self.exec_state.set(enabled);
+    }
+
+    /// Returns `true` if the execution errors carry a call stack snapshot.
+    pub(crate) fn exec_state(&self) -> bool {
+        self.exec_state.get()
+    }
+
     /// Clears loader cache.
     pub fn clear(&self) {
         *self.scripts.borrow_mut() = ScriptCache::new();
@@ -1313,6 +1343,10 @@ impl<'a> Resolver<'a> {
         self.loader.type_to_type_tag(ty)
     }
 
+    pub(crate) fn abilities(&self, ty: &Type) -> PartialVMResult<AbilitySet> {
+        self.loader.abilities(ty)
+    }
+
     //
     // Type resolution
     //
@@ -1,7 +1,12 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{native_functions::NativeFunction, runtime::VMRuntime, session::Session};
+use crate::{
+    native_functions::{DebugSink, NativeFunction, NativeHost},
+    runtime::VMRuntime,
+    session::Session,
+};
+use alloc::boxed::Box;
 use move_binary_format::errors::{Location, VMResult};
 use move_core_types::{
     account_address::AccountAddress, identifier::Identifier, resolver::MoveResolver,
@@ -13,11 +18,25 @@ pub struct MoveVM {
 
 impl MoveVM {
     pub fn new<I>(natives: I) -> VMResult<Self>
+    where
+        I: IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
+    {
+        Self::new_with_host(natives, None, None)
+    }
+
+    /// Creates a VM whose natives can query the given host environment and
+    /// pass the `Debug::print` output to the given debug sink.
+    pub fn new_with_host<I>(
+        natives: I,
+        host: Option<Box<dyn NativeHost>>,
+        debug_sink: Option<Box<dyn DebugSink>>,
+    ) -> VMResult<Self>
     where
         I: IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
     {
         Ok(Self {
-            runtime: VMRuntime::new(natives).map_err(|err| err.finish(Location::Undefined))?,
+            runtime: VMRuntime::new(natives, host, debug_sink)
+                .map_err(|err| err.finish(Location::Undefined))?,
         })
     }
 
@@ -39,6 +58,12 @@ impl MoveVM {
         self.runtime.new_session(remote)
     }
 
+    /// Enables the call stack snapshots attached to the execution errors.
+    /// Disabled by default.
+    pub fn set_exec_state(&self, enabled: bool) {
+        self.runtime.loader.set_exec_state(enabled);
+    }
+
     /// Clears vm state.
     pub fn clear(&self) {
         self.runtime.clear();
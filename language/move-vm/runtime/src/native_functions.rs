@@ -1,12 +1,17 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{interpreter::Interpreter, loader::Resolver};
+use crate::{
+    interpreter::Interpreter,
+    loader::{Function, Resolver},
+};
+use alloc::boxed::Box;
 use alloc::collections::VecDeque;
 use alloc::string::String;
 use alloc::vec::Vec;
 use hashbrown::HashMap;
 use move_binary_format::errors::{PartialVMError, PartialVMResult};
+use move_binary_format::file_format::AbilitySet;
 use move_core_types::language_storage::TypeTag;
 use move_core_types::{
     account_address::AccountAddress,
@@ -25,6 +30,38 @@ pub type NativeFunction =
 
 pub type NativeFunctionTable = Vec<(AccountAddress, Identifier, Identifier, NativeFunction)>;
 
+/// Host environment reachable from the natives.
+pub trait NativeHost {
+    /// Returns the host data associated with the `key` or an abort code.
+    fn query(&self, key: &[u8]) -> Result<Vec<u8>, u64>;
+}
+
+/// Receiver of the values printed by `Debug::print`.
+pub trait DebugSink {
+    /// Receives the printed `value` of type `ty` along with the `function` which printed it.
+    fn print(&self, function: &str, ty: &TypeTag, value: &str);
+
+    /// Receives the call stack printed by `Debug::print_stack_trace` in the `function`.
+    ///
+    /// Passes the stack trace to `print` as a `vector<u8>` value by default.
+    fn print_stack_trace(&self, function: &str, stack_trace: &str) {
+        self.print(
+            function,
+            &TypeTag::Vector(Box::new(TypeTag::U8)),
+            stack_trace,
+        )
+    }
+}
+
+impl<F> DebugSink for F
+where
+    F: Fn(&str, &TypeTag, &str),
+{
+    fn print(&self, function: &str, ty: &TypeTag, value: &str) {
+        self(function, ty, value)
+    }
+}
+
 pub struct NativeFunctions(
     HashMap<AccountAddress, HashMap<String, HashMap<String, NativeFunction>>>,
 );
@@ -63,6 +100,7 @@ pub struct NativeContext<'a> {
     data_store: &'a mut dyn DataStore,
     gas_status: &'a GasStatus<'a>,
     resolver: &'a Resolver<'a>,
+    caller: &'a Function,
 }
 
 impl<'a, 'b> NativeContext<'a> {
@@ -71,12 +109,14 @@ impl<'a, 'b> NativeContext<'a> {
         data_store: &'a mut dyn DataStore,
         gas_status: &'a mut GasStatus,
         resolver: &'a Resolver<'a>,
+        caller: &'a Function,
     ) -> Self {
         Self {
             interpreter,
             data_store,
             gas_status,
             resolver,
+            caller,
         }
     }
 }
@@ -116,4 +156,40 @@ impl<'a> NativeContext<'a> {
     pub fn type_to_type_tag(&self, ty: &Type) -> PartialVMResult<TypeTag> {
         self.resolver.type_to_type_tag(ty)
     }
+
+    pub fn abilities(&self, ty: &Type) -> PartialVMResult<AbilitySet> {
+        self.resolver.abilities(ty)
+    }
+
+    /// Queries the host environment.
+    /// Returns `None` if no host is registered with the VM.
+    pub fn host_query(&self, key: &[u8]) -> Option<Result<Vec<u8>, u64>> {
+        self.resolver.loader().host().map(|host| host.query(key))
+    }
+
+    /// Returns `true` if a debug sink is registered with the VM.
+    pub fn has_debug_sink(&self) -> bool {
+        self.resolver.loader().debug_sink().is_some()
+    }
+
+    /// Passes the `value` of type `ty` printed by the calling function to the debug sink.
+    /// Does nothing if no debug sink is registered with the VM.
+    pub fn debug_print(&self, ty: &Type, value: &str) -> PartialVMResult<()> {
+        if let Some(sink) = self.resolver.loader().debug_sink() {
+            let ty = self.type_to_type_tag(ty)?;
+            sink.print(&self.caller.pretty_string(), &ty, value);
+        }
+        Ok(())
+    }
+
+    /// Passes the call stack of the calling function to the debug sink.
+    /// Does nothing if no debug sink is registered with the VM.
+    pub fn debug_print_stack_trace(&self) -> PartialVMResult<()> {
+        if let Some(sink) = self.resolver.loader().debug_sink() {
+            let mut buf = String::new();
+            self.print_stack_trace(&mut buf)?;
+            sink.print_stack_trace(&self.caller.pretty_string(), &buf);
+        }
+        Ok(())
+    }
 }
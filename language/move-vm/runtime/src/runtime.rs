@@ -5,9 +5,10 @@ use crate::{
     data_cache::TransactionDataCache,
     interpreter::Interpreter,
     loader::Loader,
-    native_functions::{NativeFunction, NativeFunctions},
+    native_functions::{DebugSink, NativeFunction, NativeFunctions, NativeHost},
     session::Session,
 };
+use alloc::boxed::Box;
 use alloc::string::ToString;
 use alloc::vec::Vec;
 use move_binary_format::{
@@ -43,12 +44,16 @@ fn is_signer_reference(s: &Type) -> bool {
 }
 
 impl VMRuntime {
-    pub(crate) fn new<I>(natives: I) -> PartialVMResult<Self>
+    pub(crate) fn new<I>(
+        natives: I,
+        host: Option<Box<dyn NativeHost>>,
+        debug_sink: Option<Box<dyn DebugSink>>,
+    ) -> PartialVMResult<Self>
     where
         I: IntoIterator<Item = (AccountAddress, Identifier, Identifier, NativeFunction)>,
     {
         Ok(VMRuntime {
-            loader: Loader::new(NativeFunctions::new(natives)?),
+            loader: Loader::new(NativeFunctions::new(natives)?, host, debug_sink),
         })
     }
 
@@ -435,6 +435,30 @@ pub static INITIAL_GAS_SCHEDULE: Lazy<CostTable> = Lazy::new(|| {
         (N::U256_SUB, GasCost::new(10, 1)),
         (N::U256_ADD, GasCost::new(10, 1)),
         (N::TYPE_INFO, GasCost::new(10, 1)),
+        (N::HOST_QUERY, GasCost::new(10, 1)),
+        (N::SECP256K1_RECOVER, GasCost::new(61, 1)),
+        (N::SECP256K1_VERIFY, GasCost::new(61, 1)),
+        (N::KECCAK_256, GasCost::new(64, 1)),
+        (N::BLAKE2B_256, GasCost::new(21, 1)),
+        (N::U256_MOD, GasCost::new(10, 1)),
+        (N::U256_POW, GasCost::new(20, 1)),
+        (N::U256_MUL_DIV, GasCost::new(20, 1)),
+        (N::U256_SHL, GasCost::new(10, 1)),
+        (N::U256_SHR, GasCost::new(10, 1)),
+        (N::U256_AND, GasCost::new(10, 1)),
+        (N::U256_OR, GasCost::new(10, 1)),
+        (N::U256_XOR, GasCost::new(10, 1)),
+        (N::U256_CMP, GasCost::new(10, 1)),
+        (N::U256_FROM_BYTES, GasCost::new(1, 1)),
+        (N::U256_TO_BYTES, GasCost::new(1, 1)),
+        (N::BCS_FROM_BYTES, GasCost::new(181, 1)),
+        (N::TYPE_NAME, GasCost::new(10, 1)),
+        (N::TYPE_EQ, GasCost::new(10, 1)),
+        (N::APPEND, GasCost::new(227, 1)),
+        (N::REVERSE, GasCost::new(227, 1)),
+        (N::CONTAINS, GasCost::new(40, 1)),
+        (N::INDEX_OF, GasCost::new(40, 1)),
+        (N::REMOVE, GasCost::new(227, 1)),
     ];
     native_table.sort_by_key(|cost| cost.0 as u64);
     let raw_native_table = native_table
@@ -493,4 +517,34 @@ pub enum NativeCostIndex {
     U256_SUB = 26,
     U256_ADD = 27,
     TYPE_INFO = 28,
+    HOST_QUERY = 29,
+    SECP256K1_RECOVER = 30,
+    SECP256K1_VERIFY = 31,
+    KECCAK_256 = 32,
+    BLAKE2B_256 = 33,
+    U256_MOD = 34,
+    U256_POW = 35,
+    U256_MUL_DIV = 36,
+    U256_SHL = 37,
+    U256_SHR = 38,
+    U256_AND = 39,
+    U256_OR = 40,
+    U256_XOR = 41,
+    U256_CMP = 42,
+    U256_FROM_BYTES = 43,
+    U256_TO_BYTES = 44,
+    BCS_FROM_BYTES = 45,
+    TYPE_NAME = 46,
+    TYPE_EQ = 47,
+    APPEND = 48,
+    REVERSE = 49,
+    CONTAINS = 50,
+    INDEX_OF = 51,
+    REMOVE = 52,
 }
+
+/// Number of the built-in native gas entries.
+///
+/// Gas entries of the natives registered by the host are appended to the native table right
+/// after the built-in ones.
+pub const NATIVE_COST_TABLE_SIZE: usize = NativeCostIndex::REMOVE as usize + 1;
@@ -16,7 +16,11 @@
 //! This module contains the declarations and utilities to implement a native
 //! function.
 
-use crate::{gas_schedule::NativeCostIndex, values::Value};
+use crate::{
+    gas_schedule::{NativeCostIndex, NATIVE_COST_TABLE_SIZE},
+    values::Value,
+};
+use alloc::format;
 use move_core_types::gas_schedule::{
     AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, InternalGasUnits,
 };
@@ -117,6 +121,26 @@ pub fn native_gas(
     gas_amt.total().mul(memory_size)
 }
 
+/// Return the native gas entry in `CostTable` for a native function registered by the host.
+/// The key is the index of the entry in the host extension of the native table.
+/// Fails if the host did not register the entry.
+pub fn host_native_gas(
+    table: &CostTable,
+    key: usize,
+    size: usize,
+) -> PartialVMResult<InternalGasUnits<GasCarrier>> {
+    let gas_amt = NATIVE_COST_TABLE_SIZE
+        .checked_add(key)
+        .and_then(|index| table.native_table.get(index))
+        .ok_or_else(|| {
+            PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                .with_message(format!("Missing host native gas entry {}", key))
+        })?;
+    let memory_size = AbstractMemorySize::new(core::cmp::max(1, size) as GasCarrier);
+    debug_assert!(memory_size.get() > 0);
+    Ok(gas_amt.total().mul(memory_size))
+}
+
 /// Return the argument at the top of the stack.
 ///
 /// Arguments are passed to a native as a stack with first arg at the bottom of the stack.
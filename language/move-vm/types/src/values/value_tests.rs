@@ -1,7 +1,7 @@
 // Copyright (c) The Diem Core Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::values::*;
+use crate::{loaded_data::runtime_types::Type, values::*};
 use move_binary_format::errors::*;
 
 #[test]
@@ -120,3 +120,49 @@ fn global_value_non_struct() -> PartialVMResult<()> {
 
     Ok(())
 }
+
+#[test]
+fn vector_ops() -> PartialVMResult<()> {
+    let mut locals = Locals::new(1);
+    locals.store_loc(0, Value::vector_u64(vec![1, 2, 3]))?;
+    let r: VectorRef = locals.borrow_loc(0)?.value_as()?;
+
+    let other: Vector = Value::vector_u64(vec![4, 5]).value_as()?;
+    r.append(other, &Type::U64)?;
+    r.reverse(&Type::U64)?;
+    assert!(r.remove(1, &Type::U64)?.equals(&Value::u64(4))?);
+    assert!(r.remove(4, &Type::U64).is_err());
+
+    assert_eq!(r.index_of(Value::u64(3), &Type::U64)?, Some(1));
+    assert_eq!(r.index_of(Value::u64(4), &Type::U64)?, None);
+    assert!(locals
+        .copy_loc(0)?
+        .equals(&Value::vector_u64(vec![5, 3, 2, 1]))?);
+
+    Ok(())
+}
+
+#[test]
+fn vector_of_structs_ops() -> PartialVMResult<()> {
+    fn s(x: u64) -> Value {
+        Value::struct_(Struct::pack(vec![Value::u64(x)]))
+    }
+    let ty = Type::Struct(0);
+
+    let mut locals = Locals::new(1);
+    locals.store_loc(0, Vector::pack(&ty, vec![s(1), s(2)])?)?;
+    let r: VectorRef = locals.borrow_loc(0)?.value_as()?;
+
+    let other: Vector = Vector::pack(&ty, vec![s(3), s(2)])?.value_as()?;
+    r.append(other, &ty)?;
+    assert_eq!(r.index_of(s(2), &ty)?, Some(1));
+    assert_eq!(r.index_of(s(4), &ty)?, None);
+
+    assert!(r.remove(0, &ty)?.equals(&s(1))?);
+    r.reverse(&ty)?;
+    assert!(locals
+        .copy_loc(0)?
+        .equals(&Vector::pack(&ty, vec![s(2), s(3), s(2)])?)?);
+
+    Ok(())
+}
@@ -1682,6 +1682,119 @@ impl VectorRef {
         self.0.mark_dirty();
         Ok(())
     }
+
+    /// Moves the elements of `other` to the end of the vector.
+    pub fn append(&self, other: Vector, type_param: &Type) -> PartialVMResult<()> {
+        let c = self.0.container();
+        check_elem_layout(type_param, c)?;
+        check_elem_layout(type_param, &other.0)?;
+
+        match (c, other.0) {
+            (Container::VecU8(r), Container::VecU8(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (Container::VecU64(r), Container::VecU64(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (Container::VecU128(r), Container::VecU128(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (Container::VecBool(r), Container::VecBool(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (Container::VecAddress(r), Container::VecAddress(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (Container::Vec(r), Container::Vec(o)) => {
+                r.borrow_mut().extend(take_unique_ownership(o)?)
+            }
+            (c, o) => {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!("cannot append {:?} to {:?}", o, c)),
+                )
+            }
+        }
+
+        self.0.mark_dirty();
+        Ok(())
+    }
+
+    /// Reverses the order of the elements in place.
+    pub fn reverse(&self, type_param: &Type) -> PartialVMResult<()> {
+        let c = self.0.container();
+        check_elem_layout(type_param, c)?;
+
+        match c {
+            Container::VecU8(r) => r.borrow_mut().reverse(),
+            Container::VecU64(r) => r.borrow_mut().reverse(),
+            Container::VecU128(r) => r.borrow_mut().reverse(),
+            Container::VecBool(r) => r.borrow_mut().reverse(),
+            Container::VecAddress(r) => r.borrow_mut().reverse(),
+            Container::Vec(r) => r.borrow_mut().reverse(),
+            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+        }
+
+        self.0.mark_dirty();
+        Ok(())
+    }
+
+    /// Returns the index of the first element equal to `e`.
+    pub fn index_of(&self, e: Value, type_param: &Type) -> PartialVMResult<Option<usize>> {
+        let c = self.0.container();
+        check_elem_layout(type_param, c)?;
+
+        macro_rules! position {
+            ($v: expr) => {{
+                let e = e.value_as()?;
+                $v.borrow().iter().position(|x| *x == e)
+            }};
+        }
+
+        let idx = match c {
+            Container::VecU8(r) => position!(r),
+            Container::VecU64(r) => position!(r),
+            Container::VecU128(r) => position!(r),
+            Container::VecBool(r) => position!(r),
+            Container::VecAddress(r) => position!(r),
+            Container::Vec(r) => {
+                let mut idx = None;
+                for (i, x) in r.borrow().iter().enumerate() {
+                    if x.equals(&e.0)? {
+                        idx = Some(i);
+                        break;
+                    }
+                }
+                idx
+            }
+            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+        };
+        Ok(idx)
+    }
+
+    /// Removes the element at `idx` shifting all the subsequent elements.
+    pub fn remove(&self, idx: usize, type_param: &Type) -> PartialVMResult<Value> {
+        let c = self.0.container();
+        check_elem_layout(type_param, c)?;
+        if idx >= c.len() {
+            return Err(
+                PartialVMError::new(StatusCode::ABORTED).with_sub_status(INDEX_OUT_OF_BOUNDS)
+            );
+        }
+
+        let res = match c {
+            Container::VecU8(r) => Value::u8(r.borrow_mut().remove(idx)),
+            Container::VecU64(r) => Value::u64(r.borrow_mut().remove(idx)),
+            Container::VecU128(r) => Value::u128(r.borrow_mut().remove(idx)),
+            Container::VecBool(r) => Value::bool(r.borrow_mut().remove(idx)),
+            Container::VecAddress(r) => Value::address(r.borrow_mut().remove(idx)),
+            Container::Vec(r) => Value(r.borrow_mut().remove(idx)),
+            Container::Locals(_) | Container::Struct(_) => unreachable!(),
+        };
+
+        self.0.mark_dirty();
+        Ok(res)
+    }
 }
 
 impl Vector {
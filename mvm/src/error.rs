@@ -1,4 +1,6 @@
-#[derive(Debug)]
+use parity_scale_codec_derive::{Decode, Encode};
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct SubStatus {
     pub category: Category,
     pub reason: u64,
@@ -26,32 +28,43 @@ impl SubStatus {
     }
 }
 
+/// The codec indexes match the category codes.
 #[allow(non_camel_case_types)]
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
 pub enum Category {
     /// The system is in a state where the performed operation is not allowed. Example: call to a function only allowed
     /// in genesis.
+    #[codec(index = 1)]
     INVALID_STATE,
     /// The signer of a transaction does not have the expected address for this operation. Example: a call to a function
     /// which publishes a resource under a particular address.
+    #[codec(index = 2)]
     REQUIRES_ADDRESS,
     /// The signer of a transaction does not have the expected  role for this operation. Example: a call to a function
     /// which requires the signer to have the role of treasury compliance.
+    #[codec(index = 3)]
     REQUIRES_ROLE,
     /// The signer of a transaction does not have a required capability.
+    #[codec(index = 4)]
     REQUIRES_CAPABILITY,
     /// A resource is required but not published. Example: access to non-existing AccountLimits resource.
+    #[codec(index = 5)]
     NOT_PUBLISHED,
     /// Attempting to publish a resource that is already published. Example: calling an initialization function
     /// twice.
+    #[codec(index = 6)]
     ALREADY_PUBLISHED,
     /// An argument provided to an operation is invalid. Example: a signing key has the wrong format.
+    #[codec(index = 7)]
     INVALID_ARGUMENT,
     /// A limit on an amount, e.g. a currency, is exceeded. Example: withdrawal of money after account limits window
     /// is exhausted.
+    #[codec(index = 8)]
     LIMIT_EXCEEDED,
     /// An internal error (bug) has occurred.
+    #[codec(index = 10)]
     INTERNAL,
     /// A custom error category for extension points.
+    #[codec(index = 255)]
     CUSTOM,
 }
@@ -1,8 +1,11 @@
 use alloc::vec::Vec;
+use anyhow::{ensure, Error};
 use move_binary_format::file_format::Bytecode::*;
 use move_binary_format::file_format::*;
 use move_binary_format::file_format_common::instruction_key;
-use move_core_types::gas_schedule::{CostTable, GasCost};
+use move_core_types::gas_schedule::{
+    AbstractMemorySize, CostTable, GasAlgebra, GasCarrier, GasConstants, GasCost, InternalGasUnits,
+};
 use move_vm_types::gas_schedule::new_from_instructions;
 use move_vm_types::gas_schedule::NativeCostIndex as N;
 
@@ -164,6 +167,30 @@ pub fn cost_table() -> CostTable {
         (N::U256_SUB, GasCost::new(10, 1)),
         (N::U256_ADD, GasCost::new(10, 1)),
         (N::TYPE_INFO, GasCost::new(10, 1)),
+        (N::HOST_QUERY, GasCost::new(10, 1)),
+        (N::SECP256K1_RECOVER, GasCost::new(61, 1)),
+        (N::SECP256K1_VERIFY, GasCost::new(61, 1)),
+        (N::KECCAK_256, GasCost::new(64, 1)),
+        (N::BLAKE2B_256, GasCost::new(21, 1)),
+        (N::U256_MOD, GasCost::new(10, 1)),
+        (N::U256_POW, GasCost::new(20, 1)),
+        (N::U256_MUL_DIV, GasCost::new(20, 1)),
+        (N::U256_SHL, GasCost::new(10, 1)),
+        (N::U256_SHR, GasCost::new(10, 1)),
+        (N::U256_AND, GasCost::new(10, 1)),
+        (N::U256_OR, GasCost::new(10, 1)),
+        (N::U256_XOR, GasCost::new(10, 1)),
+        (N::U256_CMP, GasCost::new(10, 1)),
+        (N::U256_FROM_BYTES, GasCost::new(1, 1)),
+        (N::U256_TO_BYTES, GasCost::new(1, 1)),
+        (N::BCS_FROM_BYTES, GasCost::new(181, 1)),
+        (N::TYPE_NAME, GasCost::new(10, 1)),
+        (N::TYPE_EQ, GasCost::new(10, 1)),
+        (N::APPEND, GasCost::new(227, 1)),
+        (N::REVERSE, GasCost::new(227, 1)),
+        (N::CONTAINS, GasCost::new(40, 1)),
+        (N::INDEX_OF, GasCost::new(40, 1)),
+        (N::REMOVE, GasCost::new(227, 1)),
     ];
 
     instrs.sort_by_key(|cost| instruction_key(&cost.0));
@@ -175,3 +202,243 @@ pub fn cost_table() -> CostTable {
 
     new_from_instructions(instrs, raw_native_table)
 }
+
+/// Version of the `cost_table` schedule charging the storage writes per account.
+pub const DEFAULT_GAS_SCHEDULE_VERSION: u32 = 1;
+
+/// Version of the `cost_table` schedule charging the storage writes per byte
+/// and refunding the deleted resources.
+pub const PER_BYTE_GAS_SCHEDULE_VERSION: u32 = 2;
+
+/// Returns the `PER_BYTE_GAS_SCHEDULE_VERSION` schedule.
+/// Hosts register it with `GasSchedules::with_version` at the height of its activation.
+pub fn per_byte_gas_schedule() -> GasScheduleVersion {
+    GasScheduleVersion::new(PER_BYTE_GAS_SCHEDULE_VERSION, cost_table())
+        .with_write_set_charging(WriteSetCharging::PerByte(WriteSetCosts::default()))
+        .with_storage_refund(StorageRefund::default())
+}
+
+/// Charging of the storage writes of a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteSetCharging {
+    /// `default_account_size` bytes at `global_memory_per_byte_write_cost` per account
+    /// mutated by the transaction.
+    PerAccount,
+    /// Per byte of the written data and of the event payloads.
+    PerByte(WriteSetCosts),
+}
+
+/// Per-byte costs of the storage writes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriteSetCosts {
+    /// The cost per-byte of the new data: the bytes of the new entries and
+    /// the bytes the overwritten entries grow by.
+    pub write_cost: InternalGasUnits<GasCarrier>,
+    /// The cost per-byte of the data overwriting the existing storage entries.
+    pub overwrite_cost: InternalGasUnits<GasCarrier>,
+    /// The cost per-byte of the emitted event payloads.
+    pub event_cost: InternalGasUnits<GasCarrier>,
+}
+
+impl Default for WriteSetCosts {
+    fn default() -> Self {
+        WriteSetCosts {
+            write_cost: InternalGasUnits::new(9),
+            overwrite_cost: InternalGasUnits::new(3),
+            event_cost: InternalGasUnits::new(2),
+        }
+    }
+}
+
+/// Refund of the resources deleted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageRefund {
+    /// The refund per-byte of the deleted resources.
+    pub per_byte: InternalGasUnits<GasCarrier>,
+    /// The maximum refund of a transaction in percent of the gas used.
+    pub max_percent: GasCarrier,
+}
+
+impl Default for StorageRefund {
+    fn default() -> Self {
+        StorageRefund {
+            per_byte: InternalGasUnits::new(4),
+            max_percent: 50,
+        }
+    }
+}
+
+/// Gas schedule version active from the block height `activation_height`.
+///
+/// The write set charging and the refunds are kept out of the `cost_table`,
+/// so the encoding of the `CostTable` does not depend on the version.
+#[derive(Debug, Clone)]
+pub struct GasScheduleVersion {
+    pub version: u32,
+    pub activation_height: u64,
+    pub cost_table: CostTable,
+    pub write_set_charging: WriteSetCharging,
+    /// No refunds if `None`.
+    pub storage_refund: Option<StorageRefund>,
+}
+
+impl GasScheduleVersion {
+    /// Creates the `version` charging the storage writes per account without refunds.
+    pub fn new(version: u32, cost_table: CostTable) -> GasScheduleVersion {
+        GasScheduleVersion {
+            version,
+            activation_height: 0,
+            cost_table,
+            write_set_charging: WriteSetCharging::PerAccount,
+            storage_refund: None,
+        }
+    }
+
+    /// Sets the charging of the storage writes.
+    pub fn with_write_set_charging(mut self, charging: WriteSetCharging) -> GasScheduleVersion {
+        self.write_set_charging = charging;
+        self
+    }
+
+    /// Sets the refund of the deleted resources.
+    pub fn with_storage_refund(mut self, refund: StorageRefund) -> GasScheduleVersion {
+        self.storage_refund = Some(refund);
+        self
+    }
+
+    /// Returns the refund for the deletions of the `write_set` in gas units.
+    pub fn gas_refund(&self, write_set: &WriteSetSize, gas_used: u64) -> u64 {
+        self.storage_refund
+            .as_ref()
+            .map(|refund| write_set.refund(refund, &self.cost_table.gas_constants, gas_used))
+            .unwrap_or_default()
+    }
+}
+
+/// Gas schedule versions selected by block height, so that the blocks executed
+/// before a schedule change are replayed with the schedule they were executed with.
+#[derive(Debug, Clone)]
+pub struct GasSchedules {
+    /// Sorted by activation height, the first one is active from the genesis.
+    versions: Vec<GasScheduleVersion>,
+}
+
+impl GasSchedules {
+    /// Creates the schedules with the `schedule` active from the genesis.
+    pub fn new(mut schedule: GasScheduleVersion) -> GasSchedules {
+        schedule.activation_height = 0;
+        GasSchedules {
+            versions: vec![schedule],
+        }
+    }
+
+    /// Adds the `schedule` active from the block height `activation_height`.
+    /// The versions and the activation heights must be unique.
+    pub fn with_version(
+        mut self,
+        activation_height: u64,
+        mut schedule: GasScheduleVersion,
+    ) -> Result<GasSchedules, Error> {
+        ensure!(
+            self.versions.iter().all(|v| v.version != schedule.version),
+            "Gas schedule version {} is already registered",
+            schedule.version
+        );
+        ensure!(
+            self.versions
+                .iter()
+                .all(|v| v.activation_height != activation_height),
+            "Gas schedule activation height {} is already taken",
+            activation_height
+        );
+        let index = self
+            .versions
+            .partition_point(|v| v.activation_height < activation_height);
+        schedule.activation_height = activation_height;
+        self.versions.insert(index, schedule);
+        Ok(self)
+    }
+
+    /// Returns the version active at the block `height`.
+    pub fn at_height(&self, height: u64) -> &GasScheduleVersion {
+        let index = self
+            .versions
+            .partition_point(|v| v.activation_height <= height);
+        // The first version is active from the height 0.
+        &self.versions[index - 1]
+    }
+
+    pub(crate) fn cost_tables_mut(&mut self) -> impl Iterator<Item = &mut CostTable> {
+        self.versions.iter_mut().map(|v| &mut v.cost_table)
+    }
+}
+
+impl Default for GasSchedules {
+    fn default() -> Self {
+        GasSchedules::new(GasScheduleVersion::new(
+            DEFAULT_GAS_SCHEDULE_VERSION,
+            cost_table(),
+        ))
+    }
+}
+
+/// Sizes of the storage writes of a transaction, charged per byte.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WriteSetSize {
+    /// Bytes of the new entries and the bytes the overwritten entries grow by.
+    pub new_bytes: u64,
+    /// Bytes overwriting the stored data.
+    pub overwritten_bytes: u64,
+    /// Bytes of the event payloads.
+    pub event_bytes: u64,
+    /// Bytes of the deleted resources.
+    pub deleted_bytes: u64,
+}
+
+impl WriteSetSize {
+    /// Adds a write of `len` bytes over the stored entry of `stored_len` bytes or a new entry.
+    pub fn add_write(&mut self, stored_len: Option<usize>, len: usize) {
+        let overwritten = stored_len.unwrap_or(0).min(len);
+        self.overwritten_bytes += overwritten as u64;
+        self.new_bytes += (len - overwritten) as u64;
+    }
+
+    /// Adds an event payload of `len` bytes.
+    pub fn add_event(&mut self, len: usize) {
+        self.event_bytes += len as u64;
+    }
+
+    /// Adds a deleted resource of `stored_len` bytes.
+    pub fn add_delete(&mut self, stored_len: Option<usize>) {
+        self.deleted_bytes += stored_len.unwrap_or(0) as u64;
+    }
+
+    /// Returns the cost of the writes.
+    pub fn cost(&self, costs: &WriteSetCosts) -> InternalGasUnits<GasCarrier> {
+        costs
+            .write_cost
+            .mul(AbstractMemorySize::new(self.new_bytes))
+            .add(
+                costs
+                    .overwrite_cost
+                    .mul(AbstractMemorySize::new(self.overwritten_bytes)),
+            )
+            .add(
+                costs
+                    .event_cost
+                    .mul(AbstractMemorySize::new(self.event_bytes)),
+            )
+    }
+
+    /// Returns the refund for the deleted resources in gas units, capped at
+    /// `max_percent` percent of the `gas_used`.
+    pub fn refund(&self, refund: &StorageRefund, constants: &GasConstants, gas_used: u64) -> u64 {
+        let amount = constants.to_external_units(
+            refund
+                .per_byte
+                .mul(AbstractMemorySize::new(self.deleted_bytes)),
+        );
+        let cap = gas_used as u128 * refund.max_percent as u128 / 100;
+        amount.get().min(cap as u64)
+    }
+}
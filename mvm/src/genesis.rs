@@ -14,30 +14,40 @@ use {
     move_core_types::value::{serialize_values, MoveValue},
 };
 
-use diem_types::on_chain_config::VMConfig;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::gas_schedule::CostTable;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, TypeTag, CORE_CODE_ADDRESS};
 use move_core_types::vm_status::StatusCode;
 
-use crate::gas_schedule::cost_table;
+use crate::gas_schedule::{
+    cost_table, GasScheduleVersion, GasSchedules, DEFAULT_GAS_SCHEDULE_VERSION,
+};
 use crate::io::balance::CurrencyInfo;
-use crate::io::traits::{Balance, BalanceAccess, CurrencyAccessPath, EventHandler, Storage};
+use crate::io::traits::{
+    Balance, BalanceAccess, CurrencyAccessPath, EventHandler, FallibleStorage,
+};
 use crate::mvm::Mvm;
+use crate::natives::HostNatives;
 use crate::types::{Gas, ModulePackage, PublishPackageTx};
 use crate::Vm;
 
 pub fn init_storage<S>(storage: S, config: GenesisConfig) -> Result<(), Error>
 where
-    S: Storage,
+    S: FallibleStorage,
 {
     let fork = StorageFork::new(storage);
-    let vm_config = VMConfig {
-        gas_schedule: config.cost_table.clone(),
-    };
-    let vm = Mvm::new_with_config(&fork, NopeEventHandler, NopeBalance, vm_config)?;
-    let result = vm.publish_module_package(Gas::infinite(), config.stdlib, false);
+    let vm = Mvm::new_with_gas_schedules(
+        &fork,
+        NopeEventHandler,
+        NopeBalance,
+        config.natives,
+        GasSchedules::new(GasScheduleVersion::new(
+            DEFAULT_GAS_SCHEDULE_VERSION,
+            config.cost_table.clone(),
+        )),
+    )?;
+    let result = vm.publish_module_package(Gas::infinite(), 0, config.stdlib, false);
     ensure!(
         result.status_code == StatusCode::EXECUTED,
         "Failed to publish stdlib:{:?}",
@@ -63,8 +73,7 @@ where
         }
     }
 
-    fork.merge();
-    Ok(())
+    fork.merge()
 }
 
 // Genesis configuration.
@@ -74,6 +83,15 @@ pub struct GenesisConfig {
     pub init_func_config: Option<InitFuncConfig>,
     // Initialize function config.
     cost_table: CostTable, // Cost table.
+    natives: HostNatives,  // Host natives.
+}
+
+impl GenesisConfig {
+    /// Registers host natives required by the genesis modules.
+    pub fn with_natives(mut self, natives: HostNatives) -> GenesisConfig {
+        self.natives = natives;
+        self
+    }
 }
 
 impl Default for GenesisConfig {
@@ -97,6 +115,7 @@ impl Default for GenesisConfig {
         GenesisConfig {
             stdlib,
             cost_table,
+            natives: Default::default(),
             init_func_config: Some(InitFuncConfig {
                 module: "Genesis".as_bytes().to_vec(),
                 func: "initialize".as_bytes().to_vec(),
@@ -115,6 +134,7 @@ impl Default for GenesisConfig {
         GenesisConfig {
             stdlib,
             cost_table: cost_table(),
+            natives: Default::default(),
             init_func_config: Default::default(),
         }
     }
@@ -135,6 +155,7 @@ pub fn build_genesis_config(
     GenesisConfig {
         stdlib,
         cost_table: cost_table(),
+        natives: Default::default(),
         init_func_config,
     }
 }
@@ -158,21 +179,23 @@ impl BalanceAccess for NopeBalance {
         None
     }
 
-    fn add(&self, _: &AccountAddress, _: &CurrencyAccessPath, _: Balance) {
+    fn add(&self, _: &AccountAddress, _: &CurrencyAccessPath, _: Balance) -> Result<(), Error> {
         //no-op
+        Ok(())
     }
 
-    fn sub(&self, _: &AccountAddress, _: &CurrencyAccessPath, _: Balance) {
+    fn sub(&self, _: &AccountAddress, _: &CurrencyAccessPath, _: Balance) -> Result<(), Error> {
         //no-op
+        Ok(())
     }
 }
 
-pub struct StorageFork<S: Storage> {
+pub struct StorageFork<S: FallibleStorage> {
     inner: S,
     diff: RefCell<HashMap<Cow<'static, [u8]>, Option<Vec<u8>>>>,
 }
 
-impl<S: Storage> StorageFork<S> {
+impl<S: FallibleStorage> StorageFork<S> {
     pub fn new(storage: S) -> StorageFork<S> {
         StorageFork {
             inner: storage,
@@ -180,38 +203,37 @@ impl<S: Storage> StorageFork<S> {
         }
     }
 
-    pub fn merge(self) {
-        for (key, val) in self.diff.take() {
-            match val {
-                None => {
-                    self.inner.remove(key.as_ref());
-                }
-                Some(val) => {
-                    self.inner.insert(key.as_ref(), val.as_ref());
-                }
-            }
-        }
+    pub fn merge(self) -> Result<(), Error> {
+        self.inner.commit(
+            self.diff
+                .take()
+                .into_iter()
+                .map(|(key, val)| (key.into_owned(), val))
+                .collect(),
+        )
     }
 }
 
-impl<S: Storage> Storage for &StorageFork<S> {
-    fn get(&self, key: &[u8]) -> Option<Vec<u8>> {
+impl<S: FallibleStorage> FallibleStorage for &StorageFork<S> {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let diff = self.diff.borrow();
         if let Some(val) = diff.get(key) {
-            val.to_owned()
+            Ok(val.to_owned())
         } else {
             self.inner.get(key)
         }
     }
 
-    fn insert(&self, key: &[u8], value: &[u8]) {
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
         let mut diff = self.diff.borrow_mut();
 
         diff.insert(Cow::Owned(key.to_vec()), Some(value.to_vec()));
+        Ok(())
     }
 
-    fn remove(&self, key: &[u8]) {
+    fn remove(&self, key: &[u8]) -> Result<(), Error> {
         let mut diff = self.diff.borrow_mut();
         diff.insert(Cow::Owned(key.to_vec()), None);
+        Ok(())
     }
 }
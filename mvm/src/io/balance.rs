@@ -1,15 +1,17 @@
 use alloc::borrow::Cow;
 use alloc::borrow::ToOwned;
-use alloc::collections::BTreeMap;
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::vec::Vec;
-use anyhow::Error;
+use anyhow::{anyhow, Error};
 use cell::Lazy;
 use core::cell::RefCell;
+use core::convert::TryFrom;
+use core::fmt;
 use diem_types::account_config;
 use diem_types::account_config::{ACCOUNT_MODULE_IDENTIFIER, CORE_CODE_ADDRESS};
 use diem_types::resources::currency_info::CurrencyInfoResource;
 use hashbrown::HashMap;
-use move_binary_format::errors::{Location, VMResult};
+use move_binary_format::errors::{Location, VMError, VMResult};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::ChangeSet;
 use move_core_types::ident_str;
@@ -18,6 +20,7 @@ use move_core_types::language_storage::{StructTag, TypeTag};
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
 use move_core_types::vm_status::StatusCode;
 use move_vm_types::natives::function::PartialVMError;
+use parity_scale_codec_derive::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use crate::io::traits::{Balance, BalanceAccess, CurrencyAccessPath};
@@ -25,6 +28,7 @@ use move_core_types::vm_status::known_locations::DIEM_MODULE_IDENTIFIER;
 
 pub const DIEM_COIN_IDENTIFIER: &IdentStr = ident_str!("Balance");
 pub const CURRENCY_INFO: &str = "TokenInfo";
+pub const NATIVE_TOKEN: &str = "NativeToken";
 
 pub static BALANCE_TEMPLATE: Lazy<StructTag> = Lazy::new(|| StructTag {
     address: CORE_CODE_ADDRESS,
@@ -35,6 +39,7 @@ pub static BALANCE_TEMPLATE: Lazy<StructTag> = Lazy::new(|| StructTag {
 
 pub struct MasterOfCoin<B: BalanceAccess> {
     access: B,
+    width: BalanceWidth,
     native_mapper: RefCell<HashMap<StructTag, Option<Vec<u8>>>>,
 }
 
@@ -42,10 +47,17 @@ impl<B: BalanceAccess> MasterOfCoin<B> {
     pub fn new(access: B) -> MasterOfCoin<B> {
         MasterOfCoin {
             access,
+            width: BalanceWidth::default(),
             native_mapper: Default::default(),
         }
     }
 
+    /// Sets the width of the balance value of the Move `Balance` resource.
+    pub fn with_width(mut self, width: BalanceWidth) -> MasterOfCoin<B> {
+        self.width = width;
+        self
+    }
+
     pub fn session<'b, 'r, R: ModuleResolver<Error = Error> + ResourceResolver<Error = Error>>(
         &'b self,
         remote: &'r R,
@@ -54,6 +66,7 @@ impl<B: BalanceAccess> MasterOfCoin<B> {
             master_of_coin: self,
             balances: RefCell::new(Default::default()),
             remote,
+            out_of_range: RefCell::new(None),
         }
     }
 
@@ -61,33 +74,97 @@ impl<B: BalanceAccess> MasterOfCoin<B> {
         self.native_mapper.borrow_mut().clear();
     }
 
-    pub fn update_balance(&self, balance_op: BalanceOp) {
+    /// Applies the balance operations of a transaction all or nothing.
+    ///
+    /// The operations are checked by the host first. If the host rejects one of them while
+    /// applying, the already applied operations are reverted.
+    pub fn update_balances(&self, ops: &[BalanceOp]) -> VMResult<()> {
+        self.access.check(ops).map_err(balance_rejected)?;
+
+        for (applied, op) in ops.iter().enumerate() {
+            if let Err(err) = self.update_balance(op) {
+                self.revert_balances(&ops[..applied])?;
+                return Err(balance_rejected(err));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reverts the applied balance operations.
+    ///
+    /// Reverts as many operations as possible and returns the first failure with
+    /// `UNKNOWN_INVARIANT_VIOLATION_ERROR`: the host balances are left inconsistent then.
+    pub fn revert_balances(&self, ops: &[BalanceOp]) -> VMResult<()> {
+        let mut result = Ok(());
+        for op in ops.iter().rev() {
+            if let Err(err) = self.update_balance(&op.inverse()) {
+                if result.is_ok() {
+                    result = Err(PartialVMError::new(
+                        StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR,
+                    )
+                    .with_message(format!(
+                        "Failed to revert balance operation {:?}: {}",
+                        op, err
+                    ))
+                    .finish(Location::Undefined));
+                }
+            }
+        }
+        result
+    }
+
+    fn update_balance(&self, balance_op: &BalanceOp) -> Result<(), Error> {
         match balance_op {
-            BalanceOp::Add(acc, path, diff) => self.access.add(&acc, path.as_ref(), diff),
-            BalanceOp::Sub(acc, path, diff) => self.access.sub(&acc, path.as_ref(), diff),
+            BalanceOp::Add(acc, path, diff) => self.access.add(acc, path.as_ref(), *diff),
+            BalanceOp::Sub(acc, path, diff) => self.access.sub(acc, path.as_ref(), *diff),
         }
     }
 
-    fn get_bridge<R: ResourceResolver>(&self, remote: &R, coin: &StructTag) -> Option<Vec<u8>> {
+    fn get_bridge<R: ResourceResolver<Error = Error>>(
+        &self,
+        remote: &R,
+        coin: &StructTag,
+    ) -> Result<Option<Vec<u8>>, Error> {
         let mut mapper = self.native_mapper.borrow_mut();
 
         match mapper.get(coin) {
-            Some(path) => path.to_owned(),
+            Some(path) => Ok(path.to_owned()),
             None => {
                 let addr = account_config::diem_root_address();
                 let mut currency = native_currency(coin);
                 let bridge = remote
-                    .get_resource(&addr, &currency)
-                    .ok()?
+                    .get_resource(&addr, &currency)?
                     .and_then(|buff| bcs::from_bytes(&buff).ok());
                 if let TypeTag::Struct(coin) = currency.type_params.remove(0) {
                     mapper.insert(coin, bridge.to_owned());
                 }
-                bridge
+                Ok(bridge)
             }
         }
     }
 
+    /// Updates the bridge of the native currency `coin` with the new value of its
+    /// `NativeToken` resource. `None` means the resource was removed.
+    pub(crate) fn update_bridge(&self, coin: &StructTag, native_token: Option<&[u8]>) {
+        let bridge = native_token.and_then(|buff| bcs::from_bytes(buff).ok());
+        self.native_mapper
+            .borrow_mut()
+            .insert(coin.to_owned(), bridge);
+    }
+
+    /// Returns the native currency bridges known to the VM: the currencies looked up or
+    /// registered since the VM was created or cleared, along with their access paths.
+    pub fn bridges(&self) -> Vec<(StructTag, Vec<u8>)> {
+        let mut bridges: Vec<_> = self
+            .native_mapper
+            .borrow()
+            .iter()
+            .filter_map(|(coin, path)| path.as_ref().map(|path| (coin.to_owned(), path.to_owned())))
+            .collect();
+        bridges.sort();
+        bridges
+    }
+
     fn get_balance(&self, address: &AccountAddress, path: &CurrencyAccessPath) -> Option<Balance> {
         self.access.get_balance(address, path)
     }
@@ -117,6 +194,9 @@ pub struct MasterOfCoinSession<
     master_of_coin: &'b MasterOfCoin<B>,
     balances: RefCell<HashMap<AccountAddress, HashMap<Cow<'static, CurrencyAccessPath>, Balance>>>,
     remote: &'r R,
+    /// The balance that does not fit the balance width.
+    /// The VM reports the failed read as a storage error, the session keeps its actual cause.
+    out_of_range: RefCell<Option<VMError>>,
 }
 
 impl<
@@ -126,16 +206,42 @@ impl<
         R: ModuleResolver<Error = Error> + ResourceResolver<Error = Error>,
     > MasterOfCoinSession<'b, 'r, B, R>
 {
-    fn get_bridge(&self, coin: &StructTag) -> Option<Vec<u8>> {
-        self.master_of_coin.get_bridge(self.remote, coin)
+    fn get_bridge(&self, coin_params: &[TypeTag]) -> Result<Option<Vec<u8>>, Error> {
+        match coin_type(coin_params) {
+            Some(coin) => self.master_of_coin.get_bridge(self.remote, coin),
+            None => Ok(None),
+        }
     }
 
-    fn get_balance(&self, address: &AccountAddress, path: Vec<u8>) -> Option<Vec<u8>> {
-        let balance = self.master_of_coin.get_balance(address, &path)?;
+    fn get_balance(
+        &self,
+        address: &AccountAddress,
+        path: Vec<u8>,
+    ) -> Result<Option<Vec<u8>>, Error> {
+        let balance = match self.master_of_coin.get_balance(address, &path) {
+            Some(balance) => balance,
+            None => return Ok(None),
+        };
+        let encoded = match self.master_of_coin.width.encode(balance) {
+            Ok(encoded) => encoded,
+            Err(err) => {
+                *self.out_of_range.borrow_mut() = Some(
+                    PartialVMError::new(StatusCode::BALANCE_OUT_OF_RANGE)
+                        .with_message(err.to_string())
+                        .finish(Location::Undefined),
+                );
+                return Err(err);
+            }
+        };
         let mut balances = self.balances.borrow_mut();
         let acc = balances.entry(*address).or_insert_with(HashMap::new);
         acc.insert(Cow::Owned(path), balance);
-        bcs::to_bytes(&DiemBalance { value: balance }).ok()
+        Ok(Some(encoded))
+    }
+
+    /// Takes the error of the balance that did not fit the balance width.
+    pub fn take_out_of_range_error(&self) -> Option<VMError> {
+        self.out_of_range.borrow_mut().take()
     }
 
     pub fn resolve(
@@ -145,15 +251,15 @@ impl<
     ) -> Result<Option<Vec<u8>>, Error> {
         if tag.module.as_ref() == ACCOUNT_MODULE_IDENTIFIER {
             if tag.name.as_ref() == DIEM_COIN_IDENTIFIER {
-                let bridge = coin_type(&tag.type_params).and_then(|coin| self.get_bridge(coin));
+                let bridge = self.get_bridge(&tag.type_params)?;
                 if let Some(bridge) = bridge {
-                    return Ok(self.get_balance(address, bridge));
+                    return self.get_balance(address, bridge);
                 }
             }
         } else if tag.module.as_ref() == DIEM_MODULE_IDENTIFIER
             && tag.name.as_str() == CURRENCY_INFO
         {
-            let bridge = coin_type(&tag.type_params).and_then(|coin| self.get_bridge(coin));
+            let bridge = self.get_bridge(&tag.type_params)?;
             if let Some(bridge) = bridge {
                 return Ok(self
                     .remote
@@ -189,10 +295,77 @@ impl<
         }
 
         ops.sort();
+        self.check_total_value(&ops, changes)?;
 
         Ok(ops)
     }
 
+    /// Checks that the balance operations of every currency sum up to the change of its total value.
+    fn check_total_value(&self, ops: &[BalanceOp], changes: &ChangeSet) -> VMResult<()> {
+        let mut ops_diff: BTreeMap<&CurrencyAccessPath, ValueDiff> = BTreeMap::new();
+        for op in ops {
+            let (path, diff) = match op {
+                BalanceOp::Add(_, path, amount) => (path, ValueDiff::new(false, *amount)),
+                BalanceOp::Sub(_, path, amount) => (path, ValueDiff::new(true, *amount)),
+            };
+            let sum = ops_diff.entry(path.as_ref()).or_default();
+            *sum = sum.checked_add(diff).ok_or_else(|| {
+                PartialVMError::new(StatusCode::ARITHMETIC_ERROR)
+                    .with_message(format!("Balance operations of {:?} overflow", path))
+                    .finish(Location::Undefined)
+            })?;
+        }
+
+        let mut total_diff: BTreeMap<Vec<u8>, ValueDiff> = BTreeMap::new();
+        for acc in changes.accounts.values() {
+            for (tag, val) in &acc.resources {
+                if tag.module.as_ref() != DIEM_MODULE_IDENTIFIER
+                    || tag.name.as_str() != CURRENCY_INFO
+                {
+                    continue;
+                }
+                let bridge = self.get_bridge(&tag.type_params).map_err(|err| {
+                    PartialVMError::new(StatusCode::STORAGE_ERROR)
+                        .with_message(err.to_string())
+                        .finish(Location::Undefined)
+                })?;
+                if let (Some(bridge), Some(val)) = (bridge, val) {
+                    let info = CurrencyInfoResource::try_from_bytes(val).map_err(|_| {
+                        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                            .finish(Location::Undefined)
+                    })?;
+                    let initial_value = self
+                        .master_of_coin
+                        .get_currency_info(&bridge)
+                        .map(|info| info.total_value)
+                        .unwrap_or_default();
+                    total_diff.insert(bridge, ValueDiff::between(initial_value, info.total_value));
+                }
+            }
+        }
+
+        let paths = ops_diff
+            .keys()
+            .map(|path| path.to_vec())
+            .chain(total_diff.keys().cloned())
+            .collect::<BTreeSet<_>>();
+        for path in paths {
+            let ops_diff = ops_diff.get(path.as_slice()).copied().unwrap_or_default();
+            let total_diff = total_diff.get(&path).copied().unwrap_or_default();
+            if ops_diff != total_diff {
+                return Err(
+                    PartialVMError::new(StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR)
+                        .with_message(format!(
+                            "Balance operations of {:?} sum up to {} while its total value changed by {}",
+                            path, ops_diff, total_diff
+                        ))
+                        .finish(Location::Undefined),
+                );
+            }
+        }
+        Ok(())
+    }
+
     fn make_acc_ops(
         &self,
         ops: &mut Vec<BalanceOp>,
@@ -217,11 +390,11 @@ impl<
                     ops.push(BalanceOp::Sub(acc, path, initial_balance));
                 }
                 Some(Some(buff)) => {
-                    let current_balance = bcs::from_bytes::<DiemBalance>(&buff).map_err(|_| {
-                        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
-                            .finish(Location::Undefined)
-                    })?;
-                    let current_balance = current_balance.value;
+                    let current_balance =
+                        self.master_of_coin.width.decode(&buff).ok_or_else(|| {
+                            PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR)
+                                .finish(Location::Undefined)
+                        })?;
                     if current_balance == initial_balance {
                         continue;
                     }
@@ -248,16 +421,81 @@ fn coin_type(t_params: &[TypeTag]) -> Option<&StructTag> {
     }
 }
 
+/// Returns the native currency whose bridge is stored in the resource `tag` under the `address`.
+pub(crate) fn bridge_coin<'a>(
+    address: &AccountAddress,
+    tag: &'a StructTag,
+) -> Option<&'a StructTag> {
+    if tag.address == CORE_CODE_ADDRESS
+        && tag.module.as_str() == NATIVE_TOKEN
+        && tag.name.as_str() == NATIVE_TOKEN
+        && address == &account_config::diem_root_address()
+    {
+        coin_type(&tag.type_params)
+    } else {
+        None
+    }
+}
+
 fn native_currency(coin: &StructTag) -> StructTag {
     StructTag {
         address: CORE_CODE_ADDRESS,
-        module: Identifier::new("NativeToken").expect("Valid identifier"),
-        name: Identifier::new("NativeToken").expect("Valid identifier"),
+        module: Identifier::new(NATIVE_TOKEN).expect("Valid identifier"),
+        name: Identifier::new(NATIVE_TOKEN).expect("Valid identifier"),
         type_params: vec![TypeTag::Struct(coin.to_owned())],
     }
 }
 
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
+/// Signed change of a `u128` value.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+struct ValueDiff {
+    negative: bool,
+    amount: u128,
+}
+
+impl ValueDiff {
+    fn new(negative: bool, amount: u128) -> ValueDiff {
+        ValueDiff {
+            negative: negative && amount != 0,
+            amount,
+        }
+    }
+
+    /// Returns the change from the `initial` value to the `current` one.
+    fn between(initial: u128, current: u128) -> ValueDiff {
+        if current >= initial {
+            ValueDiff::new(false, current - initial)
+        } else {
+            ValueDiff::new(true, initial - current)
+        }
+    }
+
+    /// Returns the sum of the changes or `None` if its amount overflows `u128`.
+    fn checked_add(self, other: ValueDiff) -> Option<ValueDiff> {
+        if self.negative == other.negative {
+            Some(ValueDiff::new(
+                self.negative,
+                self.amount.checked_add(other.amount)?,
+            ))
+        } else if self.amount >= other.amount {
+            Some(ValueDiff::new(self.negative, self.amount - other.amount))
+        } else {
+            Some(ValueDiff::new(other.negative, other.amount - self.amount))
+        }
+    }
+}
+
+impl fmt::Display for ValueDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.negative {
+            write!(f, "-{}", self.amount)
+        } else {
+            write!(f, "{}", self.amount)
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct CurrencyInfo {
     pub total_value: u128,
 }
@@ -270,14 +508,65 @@ impl CurrencyInfo {
     }
 }
 
-#[derive(Ord, PartialOrd, Eq, PartialEq, Debug)]
+#[derive(Ord, PartialOrd, Eq, PartialEq, Debug, Clone, Encode, Decode)]
 pub enum BalanceOp {
     /// 'Sub' should be at first place, as Op codes will be sorted.
+    #[codec(index = 0)]
     Sub(AccountAddress, Cow<'static, CurrencyAccessPath>, Balance),
+    #[codec(index = 1)]
     Add(AccountAddress, Cow<'static, CurrencyAccessPath>, Balance),
 }
 
-#[derive(Debug, Serialize, Deserialize, Copy, Clone)]
-pub struct DiemBalance {
-    pub value: Balance,
+impl BalanceOp {
+    /// Returns the operation that undoes this one.
+    pub fn inverse(&self) -> BalanceOp {
+        match self {
+            BalanceOp::Sub(acc, path, amount) => BalanceOp::Add(*acc, path.clone(), *amount),
+            BalanceOp::Add(acc, path, amount) => BalanceOp::Sub(*acc, path.clone(), *amount),
+        }
+    }
+}
+
+fn balance_rejected(err: Error) -> VMError {
+    PartialVMError::new(StatusCode::BALANCE_REJECTED)
+        .with_message(err.to_string())
+        .finish(Location::Undefined)
+}
+
+/// Width of the balance value of the Move `Balance` resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BalanceWidth {
+    /// `u64` balances.
+    U64,
+    /// `u128` balances.
+    U128,
+}
+
+impl Default for BalanceWidth {
+    fn default() -> Self {
+        BalanceWidth::U64
+    }
+}
+
+impl BalanceWidth {
+    /// Serializes the `Balance` resource.
+    /// Returns an error if the balance does not fit the width.
+    pub fn encode(&self, balance: Balance) -> Result<Vec<u8>, Error> {
+        match self {
+            BalanceWidth::U64 => {
+                let value = u64::try_from(balance)
+                    .map_err(|_| anyhow!("Balance {} does not fit in u64", balance))?;
+                Ok(bcs::to_bytes(&value)?)
+            }
+            BalanceWidth::U128 => Ok(bcs::to_bytes(&balance)?),
+        }
+    }
+
+    /// Deserializes the `Balance` resource.
+    pub fn decode(&self, buff: &[u8]) -> Option<Balance> {
+        match self {
+            BalanceWidth::U64 => bcs::from_bytes::<u64>(buff).ok().map(Balance::from),
+            BalanceWidth::U128 => bcs::from_bytes(buff).ok(),
+        }
+    }
 }
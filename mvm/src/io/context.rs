@@ -1,16 +1,38 @@
+use alloc::boxed::Box;
 use alloc::vec::Vec;
-use move_core_types::language_storage::StructTag;
+use diem_types::account_config;
+use hashbrown::HashMap;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{StructTag, CORE_CODE_ADDRESS};
+use parity_scale_codec_derive::{Decode, Encode};
 
 pub const TIMESTAMP_MODULE: &str = "PontTimestamp";
 pub const CURRENT_TIME_MICROSECONDS: &str = "CurrentTimeMicroseconds";
 
 pub const BLOCK_MODULE: &str = "PontBlock";
 pub const BLOCK_METADATA: &str = "BlockMetadata";
+pub const PARENT_BLOCK_HASH: &str = "ParentBlockHash";
 
-#[derive(Debug)]
+pub const TRANSACTION_MODULE: &str = "PontTransaction";
+pub const TRANSACTION_HASH: &str = "TransactionHash";
+pub const TRANSACTION_SENDER: &str = "TransactionSender";
+
+pub const CHAIN_ID_MODULE: &str = "PontChainId";
+pub const CHAIN_ID: &str = "ChainId";
+
+/// Block and transaction data served to the Move code as virtual resources.
+/// The resources of the unset fields are not available.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ExecutionContext {
     pub timestamp: u64,
     pub block_height: u64,
+    pub parent_block_hash: Option<Vec<u8>>,
+    pub tx_hash: Option<Vec<u8>>,
+    /// The first signer of the transaction.
+    /// Filled by the VM from the transaction signers, the value set by the host is ignored.
+    pub sender: Option<AccountAddress>,
+    pub chain_id: Option<u8>,
 }
 
 impl ExecutionContext {
@@ -18,17 +40,126 @@ impl ExecutionContext {
         ExecutionContext {
             timestamp,
             block_height,
+            parent_block_hash: None,
+            tx_hash: None,
+            sender: None,
+            chain_id: None,
         }
     }
 
-    pub fn resolve(&self, tag: &StructTag) -> Option<Vec<u8>> {
-        if tag.module.as_str() == TIMESTAMP_MODULE && tag.name.as_str() == CURRENT_TIME_MICROSECONDS
-        {
-            bcs::to_bytes(&self.timestamp).ok()
-        } else if tag.module.as_str() == BLOCK_MODULE && tag.name.as_str() == BLOCK_METADATA {
-            bcs::to_bytes(&self.block_height).ok()
-        } else {
-            None
+    /// Sets the hash of the parent block.
+    pub fn with_parent_block_hash(mut self, hash: Vec<u8>) -> ExecutionContext {
+        self.parent_block_hash = Some(hash);
+        self
+    }
+
+    /// Sets the hash of the current transaction.
+    pub fn with_tx_hash(mut self, tx_hash: Vec<u8>) -> ExecutionContext {
+        self.tx_hash = Some(tx_hash);
+        self
+    }
+
+    /// Sets the chain id.
+    pub fn with_chain_id(mut self, chain_id: u8) -> ExecutionContext {
+        self.chain_id = Some(chain_id);
+        self
+    }
+}
+
+/// Supplies the BCS value of a virtual resource for the transaction context.
+/// Returns `None` if the resource is not available in the context.
+pub type ResourceProvider = Box<dyn Fn(&ExecutionContext) -> Option<Vec<u8>>>;
+
+/// Registry of the virtual resources: resources served by the host instead of the storage.
+///
+/// The default registry serves the Pontem stdlib resources under the root address.
+pub struct VirtualResources {
+    providers: HashMap<StructTag, HashMap<AccountAddress, ResourceProvider>>,
+}
+
+impl VirtualResources {
+    /// Creates an empty registry.
+    pub fn empty() -> VirtualResources {
+        VirtualResources {
+            providers: HashMap::new(),
         }
     }
+
+    /// Registers the provider of the resource `tag` stored under the `address`.
+    /// Replaces the previous provider of the resource.
+    pub fn register<F>(&mut self, address: AccountAddress, tag: StructTag, provider: F)
+    where
+        F: Fn(&ExecutionContext) -> Option<Vec<u8>> + 'static,
+    {
+        self.providers
+            .entry(tag)
+            .or_default()
+            .insert(address, Box::new(provider));
+    }
+
+    /// Registers the provider of the resource `tag` stored under the `address`.
+    pub fn with<F>(mut self, address: AccountAddress, tag: StructTag, provider: F) -> Self
+    where
+        F: Fn(&ExecutionContext) -> Option<Vec<u8>> + 'static,
+    {
+        self.register(address, tag, provider);
+        self
+    }
+
+    /// Returns the value of the virtual resource `tag` stored under the `address`.
+    pub fn resolve(
+        &self,
+        address: &AccountAddress,
+        tag: &StructTag,
+        context: &ExecutionContext,
+    ) -> Option<Vec<u8>> {
+        let provider = self.providers.get(tag)?.get(address)?;
+        provider(context)
+    }
+}
+
+impl Default for VirtualResources {
+    fn default() -> Self {
+        let root = account_config::diem_root_address();
+        VirtualResources::empty()
+            .with(
+                root,
+                core_tag(TIMESTAMP_MODULE, CURRENT_TIME_MICROSECONDS),
+                |ctx| bcs::to_bytes(&ctx.timestamp).ok(),
+            )
+            .with(root, core_tag(BLOCK_MODULE, BLOCK_METADATA), |ctx| {
+                bcs::to_bytes(&ctx.block_height).ok()
+            })
+            .with(root, core_tag(BLOCK_MODULE, PARENT_BLOCK_HASH), |ctx| {
+                ctx.parent_block_hash
+                    .as_ref()
+                    .and_then(|hash| bcs::to_bytes(hash).ok())
+            })
+            .with(
+                root,
+                core_tag(TRANSACTION_MODULE, TRANSACTION_HASH),
+                |ctx| {
+                    ctx.tx_hash
+                        .as_ref()
+                        .and_then(|hash| bcs::to_bytes(hash).ok())
+                },
+            )
+            .with(
+                root,
+                core_tag(TRANSACTION_MODULE, TRANSACTION_SENDER),
+                |ctx| ctx.sender.and_then(|sender| bcs::to_bytes(&sender).ok()),
+            )
+            .with(root, core_tag(CHAIN_ID_MODULE, CHAIN_ID), |ctx| {
+                ctx.chain_id.and_then(|id| bcs::to_bytes(&id).ok())
+            })
+    }
+}
+
+fn core_tag(module: &str, name: &str) -> StructTag {
+    StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new(module).expect("Valid module name"),
+        name: Identifier::new(name).expect("Valid struct name"),
+        type_params: vec![],
+    }
 }
@@ -40,6 +40,12 @@ impl From<&ModuleId> for AccessKey {
     }
 }
 
+impl From<AccessKey> for Vec<u8> {
+    fn from(key: AccessKey) -> Self {
+        key.0
+    }
+}
+
 impl AsRef<[u8]> for AccessKey {
     fn as_ref(&self) -> &[u8] {
         &self.0
@@ -1,10 +1,9 @@
 use crate::io::balance::{BalanceOp, MasterOfCoinSession};
-use crate::io::context::ExecutionContext;
+use crate::io::context::{ExecutionContext, VirtualResources};
 use crate::io::traits::BalanceAccess;
 use alloc::vec::Vec;
 use anyhow::Error;
-use diem_types::account_config;
-use move_binary_format::errors::VMResult;
+use move_binary_format::errors::{VMError, VMResult};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::{ChangeSet, Event};
 use move_core_types::language_storage::{ModuleId, StructTag, CORE_CODE_ADDRESS};
@@ -18,6 +17,7 @@ pub struct StateSession<
 > {
     remote: &'r R,
     context: Option<ExecutionContext>,
+    virtual_resources: &'b VirtualResources,
     coin_session: MasterOfCoinSession<'b, 'r, B, R>,
 }
 
@@ -31,15 +31,22 @@ impl<
     pub(crate) fn new(
         remote: &'r R,
         context: Option<ExecutionContext>,
+        virtual_resources: &'b VirtualResources,
         coin_session: MasterOfCoinSession<'b, 'r, B, R>,
     ) -> StateSession<'b, 'r, R, B> {
         StateSession {
             remote,
             context,
+            virtual_resources,
             coin_session,
         }
     }
 
+    /// Replaces the storage error of the VM with the actual cause of the failed read.
+    pub fn map_read_error(&self, err: VMError) -> VMError {
+        self.coin_session.take_out_of_range_error().unwrap_or(err)
+    }
+
     pub fn finish(
         self,
         (mut changes, events): (ChangeSet, Vec<Event>),
@@ -77,14 +84,12 @@ impl<
         address: &AccountAddress,
         tag: &StructTag,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
-        if tag.address == CORE_CODE_ADDRESS {
-            if address == &account_config::diem_root_address() {
-                if let Some(ctx) = &self.context {
-                    if let Some(blob) = ctx.resolve(tag) {
-                        return Ok(Some(blob));
-                    }
-                }
+        if let Some(ctx) = &self.context {
+            if let Some(blob) = self.virtual_resources.resolve(address, tag, ctx) {
+                return Ok(Some(blob));
             }
+        }
+        if tag.address == CORE_CODE_ADDRESS {
             if let Some(blob) = self.coin_session.resolve(address, tag)? {
                 return Ok(Some(blob));
             }
@@ -1,19 +1,19 @@
 use crate::io::balance::MasterOfCoin;
-use crate::io::context::ExecutionContext;
+use crate::io::context::{ExecutionContext, VirtualResources};
 use crate::io::key::AccessKey;
 use crate::io::session::StateSession;
-use crate::io::traits::{BalanceAccess, Storage};
+use crate::io::traits::{BalanceAccess, FallibleStorage};
 use alloc::vec::Vec;
 use anyhow::Error;
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::{ModuleId, StructTag};
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
 
-pub struct State<S: Storage> {
+pub struct State<S: FallibleStorage> {
     store: S,
 }
 
-impl<S: Storage> State<S> {
+impl<S: FallibleStorage> State<S> {
     pub fn new(store: S) -> State<S> {
         State { store }
     }
@@ -21,21 +21,27 @@ impl<S: Storage> State<S> {
     pub fn state_session<'c, B: BalanceAccess>(
         &self,
         context: Option<ExecutionContext>,
+        virtual_resources: &'c VirtualResources,
         master_of_coin: &'c MasterOfCoin<B>,
     ) -> StateSession<'c, '_, State<S>, B> {
-        StateSession::new(self, context, master_of_coin.session(self))
+        StateSession::new(
+            self,
+            context,
+            virtual_resources,
+            master_of_coin.session(self),
+        )
     }
 }
 
-impl<S: Storage> ModuleResolver for State<S> {
+impl<S: FallibleStorage> ModuleResolver for State<S> {
     type Error = Error;
 
     fn get_module(&self, module_id: &ModuleId) -> Result<Option<Vec<u8>>, Self::Error> {
-        Ok(self.store.get(AccessKey::from(module_id).as_ref()))
+        self.store.get(AccessKey::from(module_id).as_ref())
     }
 }
 
-impl<S: Storage> ResourceResolver for State<S> {
+impl<S: FallibleStorage> ResourceResolver for State<S> {
     type Error = Error;
 
     fn get_resource(
@@ -43,21 +49,22 @@ impl<S: Storage> ResourceResolver for State<S> {
         address: &AccountAddress,
         typ: &StructTag,
     ) -> Result<Option<Vec<u8>>, Self::Error> {
-        Ok(self.store.get(AccessKey::from((address, typ)).as_ref()))
+        self.store.get(AccessKey::from((address, typ)).as_ref())
     }
 }
 
-impl<S: Storage> WriteEffects for State<S> {
-    fn delete(&self, key: AccessKey) {
-        self.store.remove(key.as_ref());
-    }
-
-    fn insert(&self, key: AccessKey, blob: Vec<u8>) {
-        self.store.insert(key.as_ref(), &blob);
+impl<S: FallibleStorage> WriteEffects for State<S> {
+    fn commit(&self, changes: Vec<(AccessKey, Option<Vec<u8>>)>) -> Result<(), Error> {
+        self.store.commit(
+            changes
+                .into_iter()
+                .map(|(key, blob)| (key.into(), blob))
+                .collect(),
+        )
     }
 }
 
 pub trait WriteEffects {
-    fn delete(&self, path: AccessKey);
-    fn insert(&self, path: AccessKey, blob: Vec<u8>);
+    /// Writes the changes all or nothing: `None` deletes the entry.
+    fn commit(&self, changes: Vec<(AccessKey, Option<Vec<u8>>)>) -> Result<(), Error>;
 }
@@ -1,10 +1,33 @@
-use crate::io::balance::CurrencyInfo;
+use crate::io::balance::{BalanceOp, CurrencyInfo};
 use alloc::vec::Vec;
+use anyhow::{anyhow, Error};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::language_storage::TypeTag;
+use parity_scale_codec_derive::{Decode, Encode};
+
+/// Event emitted by a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct TxEvent {
+    /// Position of the event among the events of the transaction.
+    pub index: u32,
+    pub guid: Vec<u8>,
+    pub seq_num: u64,
+    pub ty_tag: TypeTag,
+    pub message: Vec<u8>,
+}
 
 pub trait EventHandler {
     fn on_event(&self, guid: Vec<u8>, seq_num: u64, ty_tag: TypeTag, message: Vec<u8>);
+
+    /// Receives the ordered events of the transaction `tx_index` once its effects are written
+    /// to the storage. The events of the transactions failed to commit are never delivered.
+    ///
+    /// Passes the events to `on_event` one by one by default.
+    fn on_tx_events(&self, _tx_index: u64, events: Vec<TxEvent>) {
+        for event in events {
+            self.on_event(event.guid, event.seq_num, event.ty_tag, event.message);
+        }
+    }
 }
 
 pub trait Storage {
@@ -16,12 +39,90 @@ pub trait Storage {
     fn remove(&self, key: &[u8]);
 }
 
+/// Storage that reports the failures of its backend.
+/// The errors fail the transaction with `STORAGE_ERROR`.
+pub trait FallibleStorage {
+    /// Returns the data for `key` in the storage or `None` if the key can not be found.
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error>;
+    /// Set `key` to `value` in the storage.
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<(), Error>;
+    /// Clear the storage of the given `key` and its value.
+    fn remove(&self, key: &[u8]) -> Result<(), Error>;
+
+    /// Writes the `changes` all or nothing: `None` clears the key.
+    ///
+    /// Writes the entries one by one by default and restores the written ones on a failure.
+    /// Storages with batched writes should override it.
+    fn commit(&self, changes: Vec<(Vec<u8>, Option<Vec<u8>>)>) -> Result<(), Error> {
+        let mut applied: Vec<(Vec<u8>, Option<Vec<u8>>)> = Vec::with_capacity(changes.len());
+        for (key, val) in changes {
+            let result = self.get(&key).and_then(|prev| {
+                match &val {
+                    Some(val) => self.insert(&key, val)?,
+                    None => self.remove(&key)?,
+                }
+                Ok(prev)
+            });
+            match result {
+                Ok(prev) => applied.push((key, prev)),
+                Err(err) => {
+                    for (key, prev) in applied.into_iter().rev() {
+                        match prev {
+                            Some(prev) => self.insert(&key, &prev),
+                            None => self.remove(&key),
+                        }
+                        .map_err(|restore_err| {
+                            anyhow!("{}; failed to restore {:?}: {}", err, key, restore_err)
+                        })?;
+                    }
+                    return Err(err);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Adapter for the infallible storage.
+impl<S: Storage> FallibleStorage for S {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        Ok(Storage::get(self, key))
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        Storage::insert(self, key, value);
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<(), Error> {
+        Storage::remove(self, key);
+        Ok(())
+    }
+}
+
 pub type CurrencyAccessPath = [u8];
-pub type Balance = u64;
+pub type Balance = u128;
 
 pub trait BalanceAccess {
     fn get_currency_info(&self, path: &CurrencyAccessPath) -> Option<CurrencyInfo>;
     fn get_balance(&self, address: &AccountAddress, path: &CurrencyAccessPath) -> Option<Balance>;
-    fn add(&self, address: &AccountAddress, path: &CurrencyAccessPath, amount: Balance);
-    fn sub(&self, address: &AccountAddress, path: &CurrencyAccessPath, amount: Balance);
+    /// Checks that the balance operations of a transaction can be applied.
+    /// Called before any effect of the transaction is written; an error rejects the transaction.
+    fn check(&self, _ops: &[BalanceOp]) -> Result<(), Error> {
+        Ok(())
+    }
+    /// Deposits `amount` to the balance. An error rejects the transaction.
+    fn add(
+        &self,
+        address: &AccountAddress,
+        path: &CurrencyAccessPath,
+        amount: Balance,
+    ) -> Result<(), Error>;
+    /// Withdraws `amount` from the balance. An error rejects the transaction.
+    fn sub(
+        &self,
+        address: &AccountAddress,
+        path: &CurrencyAccessPath,
+        amount: Balance,
+    ) -> Result<(), Error>;
 }
@@ -21,21 +21,37 @@ pub mod gas_schedule;
 pub mod genesis;
 pub mod io;
 pub mod mvm;
+pub mod natives;
 pub mod types;
+pub mod validation;
 
 pub trait Vm {
     /// Publishes module to the chain.
-    fn publish_module(&self, gas: Gas, module: ModuleTx, dry_run: bool) -> VmResult;
+    /// The `block_height` selects the gas schedule of the publication.
+    fn publish_module(
+        &self,
+        gas: Gas,
+        block_height: u64,
+        module: ModuleTx,
+        dry_run: bool,
+    ) -> VmResult;
 
     /// Publishes package of modules to the chain.
+    /// The `block_height` selects the gas schedule of the publication.
     fn publish_module_package(
         &self,
         gas: Gas,
+        block_height: u64,
         package: PublishPackageTx,
         dry_run: bool,
     ) -> VmResult;
 
     /// Execute script.
+    ///
+    /// Every executed transaction but the dry runs takes the next transaction index of the VM,
+    /// its events and failure event are keyed by it. The indexes start at 0 for every VM:
+    /// hosts must set the index of the first transaction with `Mvm::set_tx_index`
+    /// unless a single VM executes all the transactions of the chain.
     fn execute_script(
         &self,
         gas: Gas,
@@ -1,29 +1,35 @@
+use alloc::borrow::ToOwned;
 use alloc::vec::Vec;
+use core::cell::Cell;
 
 use anyhow::{anyhow, Error};
 
-use diem_types::on_chain_config::VMConfig;
-use move_binary_format::errors::{Location, VMError, VMResult};
+use move_binary_format::errors::{Location, PartialVMError, VMError, VMResult};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::{ChangeSet, Event};
-use move_core_types::gas_schedule::CostTable;
-use move_core_types::gas_schedule::InternalGasUnits;
-use move_core_types::gas_schedule::{AbstractMemorySize, GasAlgebra, GasUnits};
+use move_core_types::gas_schedule::{AbstractMemorySize, GasAlgebra, GasUnits, InternalGasUnits};
 use move_core_types::identifier::{IdentStr, Identifier};
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS};
-use move_core_types::vm_status::{StatusCode, VMStatus};
+use move_core_types::vm_status::{AbortLocation, StatusCode};
 use move_vm_runtime::move_vm::MoveVM;
 use move_vm_runtime::session::Session;
 
 use crate::abi::ModuleAbi;
-use crate::gas_schedule::cost_table;
-use crate::io::balance::{BalanceOp, MasterOfCoin};
-use crate::io::context::ExecutionContext;
+use crate::gas_schedule::{GasScheduleVersion, GasSchedules, WriteSetCharging, WriteSetSize};
+use crate::io::balance::{bridge_coin, BalanceOp, BalanceWidth, MasterOfCoin};
+use crate::io::context::{ExecutionContext, VirtualResources};
 use crate::io::key::AccessKey;
 use crate::io::state::{State, WriteEffects};
-use crate::io::traits::{BalanceAccess, EventHandler, Storage};
-use crate::types::{Call, Gas, ModuleTx, PublishPackageTx, ScriptTx, VmResult};
+use crate::io::traits::{BalanceAccess, EventHandler, FallibleStorage, TxEvent};
+use crate::natives::HostNatives;
+use crate::types::{
+    Call, ErrorReport, Gas, ModuleTx, PublishPackageTx, ScriptTx, StackFrame, StatusEvent,
+    VmResult, VmStatusEvent,
+};
+use crate::validation::{validate_tx, ValidationError};
 use crate::{StateAccess, Vm};
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::file_format::{CodeOffset, FunctionDefinitionIndex};
 use move_binary_format::CompiledModule;
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
 use move_vm_types::gas_schedule::GasStatus;
@@ -31,52 +37,139 @@ use move_vm_types::gas_schedule::GasStatus;
 /// MoveVM.
 pub struct Mvm<S, E, B>
 where
-    S: Storage,
+    S: FallibleStorage,
     E: EventHandler,
     B: BalanceAccess,
 {
     vm: MoveVM,
-    cost_table: CostTable,
+    gas_schedules: GasSchedules,
     state: State<S>,
     event_handler: E,
     master_of_coin: MasterOfCoin<B>,
+    virtual_resources: VirtualResources,
+    tx_index: Cell<u64>,
+    error_report: bool,
+    status_event: StatusEvent,
 }
 
 impl<S, E, B> Mvm<S, E, B>
 where
-    S: Storage,
+    S: FallibleStorage,
     E: EventHandler,
     B: BalanceAccess,
 {
     /// Creates a new move vm with given store and event handler.
     pub fn new(store: S, event_handler: E, balance: B) -> Result<Mvm<S, E, B>, Error> {
-        let vm_config = VMConfig {
-            gas_schedule: cost_table(),
-        };
+        Self::new_with_gas_schedules(
+            store,
+            event_handler,
+            balance,
+            HostNatives::default(),
+            GasSchedules::default(),
+        )
+    }
 
-        Self::new_with_config(store, event_handler, balance, vm_config)
+    /// Creates a new move vm with given store, event handler and additional host natives.
+    pub fn new_with_natives(
+        store: S,
+        event_handler: E,
+        balance: B,
+        natives: HostNatives,
+    ) -> Result<Mvm<S, E, B>, Error> {
+        Self::new_with_gas_schedules(
+            store,
+            event_handler,
+            balance,
+            natives,
+            GasSchedules::default(),
+        )
     }
 
-    pub(crate) fn new_with_config(
+    /// Creates a new move vm with given store, event handler, additional host natives and
+    /// gas schedule versions.
+    pub fn new_with_gas_schedules(
         store: S,
         event_handler: E,
         balance: B,
-        config: VMConfig,
+        natives: HostNatives,
+        mut gas_schedules: GasSchedules,
     ) -> Result<Mvm<S, E, B>, Error> {
+        let (natives, host, debug_sink) = natives.apply(gas_schedules.cost_tables_mut())?;
+
         Ok(Mvm {
-            vm: MoveVM::new(move_stdlib::natives::all_natives(CORE_CODE_ADDRESS)).map_err(
-                |err| {
-                    let (code, _, msg, _, _, _) = err.all_data();
-                    anyhow!("Error code:{:?}: msg: '{}'", code, msg.unwrap_or_default())
-                },
-            )?,
-            cost_table: config.gas_schedule,
+            vm: MoveVM::new_with_host(natives, host, debug_sink).map_err(|err| {
+                let (code, _, msg, _, _, _, _) = err.all_data();
+                anyhow!("Error code:{:?}: msg: '{}'", code, msg.unwrap_or_default())
+            })?,
+            gas_schedules,
             state: State::new(store),
             event_handler,
             master_of_coin: MasterOfCoin::new(balance),
+            virtual_resources: VirtualResources::default(),
+            tx_index: Cell::new(0),
+            error_report: false,
+            status_event: StatusEvent::default(),
         })
     }
 
+    /// Sets the width of the native balances: `u64` by default.
+    pub fn with_balance_width(mut self, width: BalanceWidth) -> Self {
+        self.master_of_coin = self.master_of_coin.with_width(width);
+        self
+    }
+
+    /// Replaces the registry of the virtual resources served instead of the storage ones.
+    pub fn with_virtual_resources(mut self, virtual_resources: VirtualResources) -> Self {
+        self.virtual_resources = virtual_resources;
+        self
+    }
+
+    /// Enables the detailed failure reports in the results: the failed function and
+    /// the call stack. Disabled by default, as it costs a call stack snapshot on each failure.
+    pub fn with_error_report(mut self, enabled: bool) -> Self {
+        self.vm.set_exec_state(enabled);
+        self.error_report = enabled;
+        self
+    }
+
+    /// Sets the event emitted for the failed transactions: `0x1::VMStatus::VMStatus` by default.
+    pub fn with_status_event(mut self, status_event: StatusEvent) -> Self {
+        self.status_event = status_event;
+        self
+    }
+
+    /// Sets the index of the next transaction reported along with its events.
+    /// The index grows by one with every transaction that is not a dry run.
+    ///
+    /// The index starts at 0 for every new VM. Hosts that create more than one VM over the
+    /// chain lifetime must set the index before the first transaction of each VM, otherwise
+    /// the failure event keys of different transactions collide.
+    pub fn set_tx_index(&self, tx_index: u64) {
+        self.tx_index.set(tx_index);
+    }
+
+    /// Returns the gas schedule active at the `block_height`.
+    fn gas_schedule(&self, block_height: u64) -> &GasScheduleVersion {
+        self.gas_schedules.at_height(block_height)
+    }
+
+    fn next_tx_index(&self) -> u64 {
+        let tx_index = self.tx_index.get();
+        self.tx_index.set(tx_index.wrapping_add(1));
+        tx_index
+    }
+
+    /// Returns the native currency bridges known to the VM along with their access paths.
+    pub fn native_bridges(&self) -> Vec<(StructTag, Vec<u8>)> {
+        self.master_of_coin.bridges()
+    }
+
+    /// Checks the type arguments, signers and arguments of the script transaction against
+    /// the signature of its entry point without executing it.
+    pub fn validate_script(&self, tx: &ScriptTx) -> Result<(), ValidationError> {
+        validate_tx(tx, |id| self.state.get_module(id))
+    }
+
     pub(crate) fn execute_function(
         &self,
         sender: AccountAddress,
@@ -87,63 +180,92 @@ where
         args: Vec<Vec<u8>>,
         context: Option<ExecutionContext>,
     ) -> VmResult {
-        let state_session = self.state.state_session(context, &self.master_of_coin);
+        let schedule = self.gas_schedule(
+            context
+                .as_ref()
+                .map(|context| context.block_height)
+                .unwrap_or_default(),
+        );
+        let state_session =
+            self.state
+                .state_session(context, &self.virtual_resources, &self.master_of_coin);
         let mut session = self.vm.new_session(&state_session);
         let mut cost_strategy =
-            GasStatus::new(&self.cost_table, GasUnits::new(gas.max_gas_amount()));
+            GasStatus::new(&schedule.cost_table, GasUnits::new(gas.max_gas_amount()));
 
-        let result =
-            session.execute_function(module, function_name, ty_args, args, &mut cost_strategy);
+        let result = session
+            .execute_function(module, function_name, ty_args, args, &mut cost_strategy)
+            .map_err(|err| state_session.map_read_error(err));
 
         self.handle_vm_result(
             sender,
+            schedule,
             cost_strategy,
             gas,
-            result.and_then(|_| session.finish().map(|(ws, e)| (ws, e, vec![]))),
+            result.and_then(|_| {
+                session
+                    .finish()
+                    .map(|(ws, e)| (ws, e, vec![], WriteSetSize::default()))
+            }),
             false,
         )
+        .with_gas_schedule_version(schedule.version)
     }
 
     /// Stores write set into storage and handle events.
+    /// The events are delivered only once all the changes are committed.
     fn handle_tx_effects(
         &self,
+        tx_index: u64,
         tx_effects: (ChangeSet, Vec<Event>, Vec<BalanceOp>),
-    ) -> Result<(), VMError> {
+    ) -> Result<Vec<BalanceOp>, VMError> {
         let (change_set, events, balance_op) = tx_effects;
 
-        for (addr, acc) in change_set.accounts {
+        // Balances go first: a rejected operation must leave no effects of the transaction.
+        self.master_of_coin.update_balances(&balance_op)?;
+
+        if let Err(err) = self.write_changes(change_set) {
+            self.master_of_coin.revert_balances(&balance_op)?;
+            return Err(storage_error(err));
+        }
+
+        let events = events
+            .into_iter()
+            .enumerate()
+            .map(|(index, (guid, seq_num, ty_tag, message))| TxEvent {
+                index: index as u32,
+                guid,
+                seq_num,
+                ty_tag,
+                message,
+            })
+            .collect();
+        self.event_handler.on_tx_events(tx_index, events);
+
+        Ok(balance_op)
+    }
+
+    /// Writes the changes of the transaction to the storage all or nothing.
+    /// The bridges of the written native currencies are updated once the changes are committed.
+    fn write_changes(&self, changes: ChangeSet) -> Result<(), Error> {
+        let mut writes = Vec::new();
+        let mut bridges = Vec::new();
+        for (addr, acc) in changes.accounts {
             for (ident, val) in acc.modules {
-                let key = AccessKey::from(&ModuleId::new(addr, ident));
-                match val {
-                    None => {
-                        self.state.delete(key);
-                    }
-                    Some(blob) => {
-                        self.state.insert(key, blob);
-                    }
-                }
+                writes.push((AccessKey::from(&ModuleId::new(addr, ident)), val));
             }
             for (tag, val) in acc.resources {
-                let key = AccessKey::from((&addr, &tag));
-                match val {
-                    None => {
-                        self.state.delete(key);
-                    }
-                    Some(blob) => {
-                        self.state.insert(key, blob);
-                    }
+                if let Some(coin) = bridge_coin(&addr, &tag) {
+                    bridges.push((coin.to_owned(), val.clone()));
                 }
+                writes.push((AccessKey::from((&addr, &tag)), val));
             }
         }
 
-        for (guid, seq_num, ty_tag, msg) in events {
-            self.event_handler.on_event(guid, seq_num, ty_tag, msg);
+        self.state.commit(writes)?;
+        for (coin, val) in bridges {
+            self.master_of_coin.update_bridge(&coin, val.as_deref());
         }
-
-        for op in balance_op.into_iter() {
-            self.master_of_coin.update_balance(op);
-        }
-
         Ok(())
     }
 
@@ -151,55 +273,155 @@ where
     fn handle_vm_result(
         &self,
         sender: AccountAddress,
+        schedule: &GasScheduleVersion,
         cost_strategy: GasStatus,
         gas_meta: Gas,
-        result: Result<(ChangeSet, Vec<Event>, Vec<BalanceOp>), VMError>,
+        result: Result<(ChangeSet, Vec<Event>, Vec<BalanceOp>, WriteSetSize), VMError>,
         dry_run: bool,
     ) -> VmResult {
         let gas_used = GasUnits::new(gas_meta.max_gas_amount)
             .sub(cost_strategy.remaining_gas())
             .get();
+        let (result, gas_refund) = match result {
+            Ok((ws, e, balance_ops, write_set)) => (
+                Ok((ws, e, balance_ops)),
+                schedule.gas_refund(&write_set, gas_used),
+            ),
+            Err(err) => (Err(err), 0),
+        };
 
         if dry_run {
             return match result {
-                Ok(_) => VmResult::new(StatusCode::EXECUTED, None, None, gas_used),
+                Ok(_) => VmResult::new(StatusCode::EXECUTED, None, None, gas_used)
+                    .with_gas_refund(gas_refund),
                 Err(err) => VmResult::new(
                     err.major_status(),
                     err.sub_status(),
                     Some(err.location().clone()),
                     gas_used,
-                ),
+                )
+                .with_error_report(self.error_report(&err)),
             };
         }
 
-        match result.and_then(|e| self.handle_tx_effects(e)) {
-            Ok(_) => VmResult::new(StatusCode::EXECUTED, None, None, gas_used),
+        let tx_index = self.next_tx_index();
+        match result.and_then(|e| self.handle_tx_effects(tx_index, e)) {
+            Ok(balance_ops) => VmResult::new(StatusCode::EXECUTED, None, None, gas_used)
+                .with_gas_refund(gas_refund)
+                .with_balance_ops(balance_ops),
             Err(err) => {
                 let status = err.major_status();
                 let sub_status = err.sub_status();
                 let loc = err.location().clone();
-                if let Err(err) = self.emit_vm_status_event(sender, err.into_vm_status()) {
+                let error_report = self.error_report(&err);
+                let event = VmStatusEvent {
+                    tx_index,
+                    sender,
+                    status: err.into_vm_status(),
+                    sub_status,
+                    location: match &loc {
+                        Location::Undefined => None,
+                        Location::Script => Some(AbortLocation::Script),
+                        Location::Module(id) => Some(AbortLocation::Module(id.clone())),
+                    },
+                    gas_used,
+                };
+                if let Err(err) = self.emit_vm_status_event(event) {
                     log::warn!("Failed to emit vm status event:{:?}", err);
                 }
                 VmResult::new(status, sub_status, Some(loc), gas_used)
+                    .with_error_report(error_report)
             }
         }
     }
 
-    fn emit_vm_status_event(&self, sender: AccountAddress, status: VMStatus) -> Result<(), Error> {
-        let tag = TypeTag::Struct(StructTag {
-            address: CORE_CODE_ADDRESS,
-            module: Identifier::new("VMStatus").unwrap(),
-            name: Identifier::new("VMStatus").unwrap(),
-            type_params: vec![],
+    /// Builds the detailed report of the failure if enabled.
+    fn error_report(&self, err: &VMError) -> Option<ErrorReport> {
+        if !self.error_report {
+            return None;
+        }
+
+        let module = match err.location() {
+            Location::Module(id) => Some(Some(id.clone())),
+            Location::Script => Some(None),
+            Location::Undefined => None,
+        };
+        let frame = module.and_then(|module| {
+            let (function, code_offset) = err.offsets().last()?;
+            Some(self.stack_frame(module, *function, *code_offset))
         });
+        let stack_trace = err
+            .exec_state()
+            .map(|state| {
+                state
+                    .stack_trace()
+                    .iter()
+                    .map(|(module, function, code_offset)| {
+                        self.stack_frame(module.clone(), *function, *code_offset)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(ErrorReport {
+            frame,
+            stack_trace,
+            message: err.message().cloned(),
+        })
+    }
+
+    fn stack_frame(
+        &self,
+        module: Option<ModuleId>,
+        function: FunctionDefinitionIndex,
+        code_offset: CodeOffset,
+    ) -> StackFrame {
+        let name = module
+            .as_ref()
+            .and_then(|id| self.state.get_module(id).ok().flatten())
+            .and_then(|code| CompiledModule::deserialize(&code).ok())
+            .and_then(|module| {
+                let def = module.function_defs().get(function.0 as usize)?;
+                let handle = module.function_handle_at(def.function);
+                Some(module.identifier_at(handle.name).to_owned())
+            });
+        StackFrame {
+            module,
+            function: name,
+            function_index: function.0,
+            code_offset,
+        }
+    }
+
+    fn emit_vm_status_event(&self, event: VmStatusEvent) -> Result<(), Error> {
+        let tag = match &self.status_event {
+            StatusEvent::Disabled => {
+                self.event_handler.on_tx_events(event.tx_index, vec![]);
+                return Ok(());
+            }
+            StatusEvent::VmStatus => TypeTag::Struct(StructTag {
+                address: CORE_CODE_ADDRESS,
+                module: Identifier::new("VMStatus").unwrap(),
+                name: Identifier::new("VMStatusEvent").unwrap(),
+                type_params: vec![],
+            }),
+            StatusEvent::Custom(tag) => tag.clone(),
+        };
 
-        let msg = bcs::to_bytes(&status)
+        let msg = bcs::to_bytes(&event)
             .map_err(|err| Error::msg(format!("Failed to generate event message: {:?}", err)))?;
 
-        let mut guid = 0_u64.to_le_bytes().to_vec();
-        guid.extend(&sender.to_u8());
-        self.event_handler.on_event(guid, 0, tag, msg);
+        let mut guid = event.tx_index.to_le_bytes().to_vec();
+        guid.extend(&event.sender.to_u8());
+        let tx_event = TxEvent {
+            index: 0,
+            guid,
+            seq_num: 0,
+            ty_tag: tag,
+            message: msg,
+        };
+        self.event_handler
+            .on_tx_events(event.tx_index, vec![tx_event]);
         Ok(())
     }
 
@@ -208,6 +430,7 @@ where
         session: &mut Session<'_, '_, R>,
         module: Vec<Vec<u8>>,
         sender: AccountAddress,
+        schedule: &GasScheduleVersion,
         cost_strategy: &mut GasStatus,
     ) -> VMResult<()>
     where
@@ -215,11 +438,14 @@ where
     {
         cost_strategy.charge_intrinsic_gas(AbstractMemorySize::new(module.len() as u64))?;
         let result = session.publish_module_bundle(module, sender, cost_strategy);
-        Self::charge_global_write_gas_usage(cost_strategy, session, &sender)?;
+        Self::charge_global_write_gas_usage(schedule, cost_strategy, session, &sender)?;
         result
     }
 
+    /// Charges `default_account_size` bytes of writes per account mutated in the `session`
+    /// if the `schedule` charges the storage writes per account.
     fn charge_global_write_gas_usage<R>(
+        schedule: &GasScheduleVersion,
         cost_strategy: &mut GasStatus,
         session: &mut Session<'_, '_, R>,
         sender: &AccountAddress,
@@ -227,6 +453,10 @@ where
     where
         R: ModuleResolver<Error = Error> + ResourceResolver<Error = Error>,
     {
+        if schedule.write_set_charging != WriteSetCharging::PerAccount {
+            return Ok(());
+        }
+
         let total_cost = session.num_mutated_accounts(sender)
             * cost_strategy
                 .cost_table()
@@ -243,43 +473,117 @@ where
             .deduct_gas(InternalGasUnits::new(total_cost))
             .map_err(|p_err| p_err.finish(Location::Undefined))
     }
+
+    /// Measures the storage writes of the transaction: the new and the overwritten data of the
+    /// modules and resources in the `changes`, the payloads of the `events` and the deletions.
+    /// Charges them per byte if the `schedule` does, the deletions are free.
+    /// Nothing is measured if the `schedule` neither charges per byte nor refunds.
+    fn charge_write_set_gas(
+        &self,
+        schedule: &GasScheduleVersion,
+        cost_strategy: &mut GasStatus,
+        changes: &ChangeSet,
+        events: &[Event],
+    ) -> VMResult<WriteSetSize> {
+        let mut size = WriteSetSize::default();
+        if schedule.write_set_charging == WriteSetCharging::PerAccount
+            && schedule.storage_refund.is_none()
+        {
+            return Ok(size);
+        }
+
+        for (addr, acc) in &changes.accounts {
+            for (ident, val) in &acc.modules {
+                if let Some(blob) = val {
+                    let stored = self
+                        .state
+                        .get_module(&ModuleId::new(*addr, ident.clone()))
+                        .map_err(storage_error)?;
+                    size.add_write(stored.map(|stored| stored.len()), blob.len());
+                }
+            }
+            for (tag, val) in &acc.resources {
+                let stored = self.state.get_resource(addr, tag).map_err(storage_error)?;
+                let stored_len = stored.map(|stored| stored.len());
+                match val {
+                    Some(blob) => size.add_write(stored_len, blob.len()),
+                    None => size.add_delete(stored_len),
+                }
+            }
+        }
+        for (_, _, _, message) in events {
+            size.add_event(message.len());
+        }
+
+        if let WriteSetCharging::PerByte(costs) = &schedule.write_set_charging {
+            cost_strategy
+                .deduct_gas(size.cost(costs))
+                .map_err(|p_err| p_err.finish(Location::Undefined))?;
+        }
+        Ok(size)
+    }
 }
 
 impl<S, E, B> Vm for Mvm<S, E, B>
 where
-    S: Storage,
+    S: FallibleStorage,
     E: EventHandler,
     B: BalanceAccess,
 {
-    fn publish_module(&self, gas: Gas, module: ModuleTx, dry_run: bool) -> VmResult {
+    fn publish_module(
+        &self,
+        gas: Gas,
+        block_height: u64,
+        module: ModuleTx,
+        dry_run: bool,
+    ) -> VmResult {
         let (module, sender) = module.into_inner();
+        let schedule = self.gas_schedule(block_height);
         let mut cost_strategy =
-            GasStatus::new(&self.cost_table, GasUnits::new(gas.max_gas_amount()));
+            GasStatus::new(&schedule.cost_table, GasUnits::new(gas.max_gas_amount()));
         let mut session = self.vm.new_session(&self.state);
 
         let result = self
-            ._publish_module(&mut session, vec![module], sender, &mut cost_strategy)
-            .and_then(|_| session.finish().map(|(ws, e)| (ws, e, vec![])));
-
-        self.handle_vm_result(sender, cost_strategy, gas, result, dry_run)
+            ._publish_module(
+                &mut session,
+                vec![module],
+                sender,
+                schedule,
+                &mut cost_strategy,
+            )
+            .and_then(|_| session.finish())
+            .and_then(|(ws, e)| {
+                let write_set = self.charge_write_set_gas(schedule, &mut cost_strategy, &ws, &e)?;
+                Ok((ws, e, vec![], write_set))
+            });
+
+        self.handle_vm_result(sender, schedule, cost_strategy, gas, result, dry_run)
+            .with_gas_schedule_version(schedule.version)
     }
 
     fn publish_module_package(
         &self,
         gas: Gas,
+        block_height: u64,
         package: PublishPackageTx,
         dry_run: bool,
     ) -> VmResult {
         let (modules, sender) = package.into_inner();
+        let schedule = self.gas_schedule(block_height);
         let mut cost_strategy =
-            GasStatus::new(&self.cost_table, GasUnits::new(gas.max_gas_amount()));
+            GasStatus::new(&schedule.cost_table, GasUnits::new(gas.max_gas_amount()));
 
         let mut session = self.vm.new_session(&self.state);
         let result = self
-            ._publish_module(&mut session, modules, sender, &mut cost_strategy)
-            .and_then(|_| session.finish().map(|(ws, e)| (ws, e, vec![])));
-
-        self.handle_vm_result(sender, cost_strategy, gas, result, dry_run)
+            ._publish_module(&mut session, modules, sender, schedule, &mut cost_strategy)
+            .and_then(|_| session.finish())
+            .and_then(|(ws, e)| {
+                let write_set = self.charge_write_set_gas(schedule, &mut cost_strategy, &ws, &e)?;
+                Ok((ws, e, vec![], write_set))
+            });
+
+        self.handle_vm_result(sender, schedule, cost_strategy, gas, result, dry_run)
+            .with_gas_schedule_version(schedule.version)
     }
 
     fn execute_script(
@@ -289,16 +593,21 @@ where
         tx: ScriptTx,
         dry_run: bool,
     ) -> VmResult {
-        let state_session = self
-            .state
-            .state_session(Some(context), &self.master_of_coin);
-        let mut vm_session = self.vm.new_session(&state_session);
-
         let (script, args, type_args, senders) = tx.into_inner();
         let sender = senders.get(0).cloned().unwrap_or(AccountAddress::ZERO);
 
+        let context = ExecutionContext {
+            sender: senders.first().cloned(),
+            ..context
+        };
+        let schedule = self.gas_schedule(context.block_height);
+        let state_session =
+            self.state
+                .state_session(Some(context), &self.virtual_resources, &self.master_of_coin);
+        let mut vm_session = self.vm.new_session(&state_session);
+
         let mut cost_strategy =
-            GasStatus::new(&self.cost_table, GasUnits::new(gas.max_gas_amount()));
+            GasStatus::new(&schedule.cost_table, GasUnits::new(gas.max_gas_amount()));
 
         let result = match script {
             Call::Script { code } => {
@@ -319,13 +628,24 @@ where
         };
 
         let exec_result = result
+            .map_err(|err| state_session.map_read_error(err))
             .and_then(|_| {
-                Self::charge_global_write_gas_usage(&mut cost_strategy, &mut vm_session, &sender)
+                Self::charge_global_write_gas_usage(
+                    schedule,
+                    &mut cost_strategy,
+                    &mut vm_session,
+                    &sender,
+                )
             })
             .and_then(|_| vm_session.finish())
-            .and_then(|vm_effects| state_session.finish(vm_effects));
-
-        self.handle_vm_result(sender, cost_strategy, gas, exec_result, dry_run)
+            .and_then(|vm_effects| state_session.finish(vm_effects))
+            .and_then(|(ws, e, balance_ops)| {
+                let write_set = self.charge_write_set_gas(schedule, &mut cost_strategy, &ws, &e)?;
+                Ok((ws, e, balance_ops, write_set))
+            });
+
+        self.handle_vm_result(sender, schedule, cost_strategy, gas, exec_result, dry_run)
+            .with_gas_schedule_version(schedule.version)
     }
 
     fn clear(&self) {
@@ -336,7 +656,7 @@ where
 
 impl<S, E, B> StateAccess for Mvm<S, E, B>
 where
-    S: Storage,
+    S: FallibleStorage,
     E: EventHandler,
     B: BalanceAccess,
 {
@@ -361,7 +681,15 @@ where
     fn get_resource(&self, address: &AccountAddress, tag: &[u8]) -> Result<Option<Vec<u8>>, Error> {
         let tag = bcs::from_bytes(tag).map_err(Error::msg)?;
 
-        let state_session = self.state.state_session(None, &self.master_of_coin);
+        let state_session =
+            self.state
+                .state_session(None, &self.virtual_resources, &self.master_of_coin);
         state_session.get_resource(address, &tag)
     }
 }
+
+fn storage_error(err: Error) -> VMError {
+    PartialVMError::new(StatusCode::STORAGE_ERROR)
+        .with_message(err.to_string())
+        .finish(Location::Undefined)
+}
@@ -0,0 +1,144 @@
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+
+use anyhow::{ensure, Error};
+
+use move_core_types::gas_schedule::{CostTable, GasCost};
+use move_core_types::language_storage::{TypeTag, CORE_CODE_ADDRESS};
+use move_vm_runtime::native_functions::{DebugSink, NativeFunctionTable, NativeHost};
+use move_vm_types::gas_schedule::NATIVE_COST_TABLE_SIZE;
+
+/// Host extension of the natives and the native gas table.
+#[derive(Default)]
+pub struct HostNatives {
+    /// Native functions registered by the host.
+    pub natives: NativeFunctionTable,
+    /// Native gas table extension.
+    /// Host natives charge the entry `i` through `move_vm_types::natives::function::host_native_gas`.
+    pub costs: Vec<GasCost>,
+    /// Host environment queried by `Host::query`.
+    pub host: Option<Box<dyn NativeHost>>,
+    /// Receiver of the `Debug::print` output.
+    pub debug: DebugOutput,
+}
+
+/// Receiver of the `Debug::print` output.
+/// The gas charged by `Debug::print` does not depend on the receiver.
+pub enum DebugOutput {
+    /// The output is discarded.
+    Disabled,
+    /// The output is written to the `log` crate at the debug level.
+    Log,
+    /// The output is passed to the host callback.
+    Host(Box<dyn DebugSink>),
+}
+
+impl Default for DebugOutput {
+    fn default() -> Self {
+        DebugOutput::Disabled
+    }
+}
+
+impl DebugOutput {
+    fn into_sink(self) -> Option<Box<dyn DebugSink>> {
+        match self {
+            DebugOutput::Disabled => None,
+            DebugOutput::Log => Some(Box::new(LogDebugSink)),
+            DebugOutput::Host(sink) => Some(sink),
+        }
+    }
+}
+
+/// Writes the debug output to the `log` crate.
+struct LogDebugSink;
+
+impl DebugSink for LogDebugSink {
+    fn print(&self, function: &str, ty: &TypeTag, value: &str) {
+        log::debug!("[{}] {}: {}", function, ty, value);
+    }
+
+    fn print_stack_trace(&self, function: &str, stack_trace: &str) {
+        log::debug!("[{}] stack trace:\n{}", function, stack_trace);
+    }
+}
+
+/// Built-in natives extended with the host natives along with the host environment
+/// and the debug sink.
+pub(crate) type Natives = (
+    NativeFunctionTable,
+    Option<Box<dyn NativeHost>>,
+    Option<Box<dyn DebugSink>>,
+);
+
+impl HostNatives {
+    /// Constructor.
+    pub fn new(natives: NativeFunctionTable, costs: Vec<GasCost>) -> HostNatives {
+        HostNatives {
+            natives,
+            costs,
+            host: None,
+            debug: DebugOutput::Disabled,
+        }
+    }
+
+    /// Registers the host environment available to the natives.
+    pub fn with_host<H: NativeHost + 'static>(mut self, host: H) -> HostNatives {
+        self.host = Some(Box::new(host));
+        self
+    }
+
+    /// Sets the receiver of the `Debug::print` output.
+    pub fn with_debug(mut self, debug: DebugOutput) -> HostNatives {
+        self.debug = debug;
+        self
+    }
+
+    /// Returns the built-in natives extended with the host natives along with the host
+    /// environment and the debug sink and appends the host native gas entries to the native
+    /// tables of the `cost_tables`.
+    ///
+    /// Host natives must be unique and must not collide with the built-in ones.
+    pub(crate) fn apply<'a>(
+        self,
+        cost_tables: impl IntoIterator<Item = &'a mut CostTable>,
+    ) -> Result<Natives, Error> {
+        let mut natives = move_stdlib::natives::all_natives(CORE_CODE_ADDRESS);
+        for (i, (addr, module, func, _)) in self.natives.iter().enumerate() {
+            let same = |(n_addr, n_module, n_func, _): &(_, _, _, _)| {
+                n_addr == addr && n_module == module && n_func == func
+            };
+            ensure!(
+                !natives.iter().any(same),
+                "Native function {}::{}::{} collides with the built-in native",
+                addr,
+                module,
+                func
+            );
+            ensure!(
+                !self.natives[..i].iter().any(same),
+                "Native function {}::{}::{} is registered twice",
+                addr,
+                module,
+                func
+            );
+        }
+        natives.extend(self.natives);
+
+        ensure!(
+            NATIVE_COST_TABLE_SIZE + self.costs.len() <= u8::MAX as usize + 1,
+            "Too many host native gas entries: {}",
+            self.costs.len()
+        );
+        for cost_table in cost_tables {
+            ensure!(
+                cost_table.native_table.len() == NATIVE_COST_TABLE_SIZE,
+                "Expected {} built-in native gas entries, got {}",
+                NATIVE_COST_TABLE_SIZE,
+                cost_table.native_table.len()
+            );
+            cost_table.native_table.extend(self.costs.iter().cloned());
+        }
+
+        Ok((natives, self.host, self.debug.into_sink()))
+    }
+}
@@ -4,15 +4,18 @@ use core::convert::TryFrom;
 use core::fmt;
 
 use anyhow::*;
+use parity_scale_codec::{Decode, Error as CodecError, Input};
 use parity_scale_codec_derive::{Decode, Encode};
 use serde::{Deserialize, Serialize};
 
 use move_core_types::account_address::AccountAddress;
-use move_core_types::language_storage::TypeTag;
+use move_core_types::language_storage::{ModuleId, TypeTag};
 use move_core_types::value::MoveValue;
-use move_core_types::vm_status::StatusCode;
+use move_core_types::vm_status::{AbortLocation, StatusCode, VMStatus};
 
 use crate::error::SubStatus;
+use crate::gas_schedule::DEFAULT_GAS_SCHEDULE_VERSION;
+use crate::io::balance::BalanceOp;
 use diem_types::account_config::diem_root_address;
 use move_binary_format::errors::Location;
 use move_core_types::identifier::Identifier;
@@ -20,7 +23,7 @@ use move_core_types::identifier::Identifier;
 const GAS_AMOUNT_MAX_VALUE: u64 = u64::MAX / 1000;
 
 /// Stores gas metadata for vm execution.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode)]
 pub struct Gas {
     /// Max gas units to be used in transaction execution.
     pub(crate) max_gas_amount: u64,
@@ -62,8 +65,23 @@ impl Gas {
     }
 }
 
+/// Rejects the gas amounts above the `Gas::infinite` limit.
+impl Decode for Gas {
+    fn decode<I: Input>(input: &mut I) -> core::result::Result<Self, CodecError> {
+        let max_gas_amount = u64::decode(input)?;
+        let gas_unit_price = u64::decode(input)?;
+        if max_gas_amount > GAS_AMOUNT_MAX_VALUE {
+            return Err("max_gas_amount value is out of range".into());
+        }
+        core::result::Result::Ok(Gas {
+            max_gas_amount,
+            gas_unit_price,
+        })
+    }
+}
+
 /// Module transaction.
-#[derive(Clone, Encode, Decode)]
+#[derive(Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ModuleTx {
     code: Vec<u8>,
     sender: AccountAddress,
@@ -96,6 +114,7 @@ impl fmt::Debug for ModuleTx {
 }
 
 /// Script bytecode + passed arguments and type parameters.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ScriptTx {
     call: Call,
     args: Vec<Vec<u8>>,
@@ -153,7 +172,7 @@ impl ScriptTx {
 }
 
 /// Move VM result.
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct VmResult {
     /// Execution status code.
     pub status_code: StatusCode,
@@ -161,8 +180,16 @@ pub struct VmResult {
     pub sub_status: Option<SubStatus>,
     /// Gas used.
     pub gas_used: u64,
+    /// Gas refunded for the deleted resources, not subtracted from `gas_used`.
+    pub gas_refund: u64,
+    /// Version of the gas schedule the transaction was charged with.
+    pub gas_schedule_version: u32,
     /// Error location
     pub location: Option<Location>,
+    /// Balance operations applied by the transaction.
+    pub balance_ops: Vec<BalanceOp>,
+    /// Detailed report of the failure, if enabled.
+    pub error_report: Option<ErrorReport>,
 }
 
 impl VmResult {
@@ -177,22 +204,126 @@ impl VmResult {
             status_code,
             sub_status: sub_status.map(SubStatus::new),
             gas_used,
+            gas_refund: 0,
+            gas_schedule_version: DEFAULT_GAS_SCHEDULE_VERSION,
             location,
+            balance_ops: vec![],
+            error_report: None,
         }
     }
+
+    /// Sets the gas refund.
+    pub(crate) fn with_gas_refund(mut self, gas_refund: u64) -> VmResult {
+        self.gas_refund = gas_refund;
+        self
+    }
+
+    /// Sets the version of the gas schedule.
+    pub(crate) fn with_gas_schedule_version(mut self, version: u32) -> VmResult {
+        self.gas_schedule_version = version;
+        self
+    }
+
+    /// Sets the applied balance operations.
+    pub(crate) fn with_balance_ops(mut self, balance_ops: Vec<BalanceOp>) -> VmResult {
+        self.balance_ops = balance_ops;
+        self
+    }
+
+    /// Sets the detailed report of the failure.
+    pub(crate) fn with_error_report(mut self, error_report: Option<ErrorReport>) -> VmResult {
+        self.error_report = error_report;
+        self
+    }
+}
+
+/// Event emitted for the failed transactions.
+#[derive(Debug, Clone)]
+pub enum StatusEvent {
+    /// No event.
+    Disabled,
+    /// `0x1::VMStatus::VMStatusEvent` event with the `VmStatusEvent` payload.
+    ///
+    /// The previous `0x1::VMStatus::VMStatus` event carried the bare `VMStatus`,
+    /// the new name keeps its decoders from misreading the new payload.
+    VmStatus,
+    /// Event of the given type.
+    Custom(TypeTag),
+}
+
+impl Default for StatusEvent {
+    fn default() -> Self {
+        StatusEvent::VmStatus
+    }
+}
+
+/// Payload of the failure event, encoded with BCS.
+///
+/// The event key is the transaction index (`u64` little endian) followed by the sender address,
+/// so it is unique per transaction as long as the transaction indexes are.
+/// See `Mvm::set_tx_index`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Encode, Decode)]
+pub struct VmStatusEvent {
+    /// Index of the failed transaction.
+    pub tx_index: u64,
+    /// Transaction sender.
+    pub sender: AccountAddress,
+    /// VM status of the transaction.
+    pub status: VMStatus,
+    /// Sub status of the failure: the abort code for the aborts.
+    pub sub_status: Option<u64>,
+    /// Location of the failure or `None` if it happened outside of the Move code.
+    pub location: Option<AbortLocation>,
+    /// Gas used by the transaction.
+    pub gas_used: u64,
+}
+
+/// Frame of the Move call stack.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct StackFrame {
+    /// Module of the function or `None` for the script.
+    pub module: Option<ModuleId>,
+    /// Function name or `None` if it can not be resolved.
+    pub function: Option<Identifier>,
+    /// Function definition index in the module.
+    pub function_index: u16,
+    /// Offset of the current instruction.
+    pub code_offset: u16,
+}
+
+/// Detailed report of a failed transaction.
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct ErrorReport {
+    /// Frame of the failed instruction or `None` if the error has no code location.
+    pub frame: Option<StackFrame>,
+    /// Call stack at the failure, outermost first.
+    /// Empty if the error happened outside the interpreter.
+    pub stack_trace: Vec<StackFrame>,
+    /// Error message.
+    pub message: Option<String>,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, PartialOrd, Encode, Decode)]
 pub enum ScriptArg {
+    #[codec(index = 0)]
     U8(u8),
+    #[codec(index = 1)]
     U64(u64),
+    #[codec(index = 2)]
     U128(u128),
+    #[codec(index = 3)]
     Bool(bool),
+    #[codec(index = 4)]
     Address(AccountAddress),
+    #[codec(index = 5)]
     VectorU8(Vec<u8>),
+    #[codec(index = 6)]
     VectorU64(Vec<u64>),
+    #[codec(index = 7)]
     VectorU128(Vec<u128>),
+    #[codec(index = 8)]
     VectorBool(Vec<bool>),
+    #[codec(index = 9)]
     VectorAddress(Vec<AccountAddress>),
 }
 
@@ -222,25 +353,29 @@ impl From<ScriptArg> for MoveValue {
 }
 
 /// Signer type.
-#[derive(Serialize, Deserialize, Debug, Ord, PartialOrd, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Encode, Decode)]
 pub enum Signer {
     /// Root signer.
+    #[codec(index = 0)]
     Root,
     /// Template to replace.
+    #[codec(index = 1)]
     Placeholder,
     /// Named address.
+    #[codec(index = 2)]
     Name(String),
 }
 
 /// Transaction model.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum Transaction {
     /// Version 1.
+    #[codec(index = 0)]
     V1(TxV1),
 }
 
 /// Transaction model.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct TxV1 {
     /// Signers.
     pub signers: Vec<Signer>,
@@ -253,14 +388,16 @@ pub struct TxV1 {
 }
 
 /// Call declaration.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub enum Call {
     /// Script
+    #[codec(index = 0)]
     Script {
         /// Script bytecode.
         code: Vec<u8>,
     },
     /// Function in module with script viability.
+    #[codec(index = 1)]
     ScriptFunction {
         /// Module address.
         mod_address: AccountAddress,
@@ -335,7 +472,7 @@ impl TryFrom<&[u8]> for Transaction {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, Default)]
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct ModulePackage {
     modules: Vec<Vec<u8>>,
 }
@@ -361,7 +498,7 @@ impl TryFrom<&[u8]> for ModulePackage {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
 pub struct PublishPackageTx {
     modules: Vec<Vec<u8>>,
     address: AccountAddress,
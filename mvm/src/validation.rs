@@ -0,0 +1,199 @@
+use alloc::borrow::ToOwned;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use anyhow::Error;
+
+use move_binary_format::access::{ModuleAccess, ScriptAccess};
+use move_binary_format::file_format::{CompiledScript, SignatureToken, Visibility};
+use move_binary_format::file_format_common::VERSION_1;
+use move_binary_format::CompiledModule;
+use move_core_types::account_address::AccountAddress;
+use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, TypeTag};
+use move_core_types::value::{MoveTypeLayout, MoveValue};
+
+use crate::types::{Call, ScriptTx};
+
+/// Transaction validation error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// Script bytecode could not be deserialized.
+    InvalidScript,
+    /// Module of the script function is not published.
+    ModuleNotFound(ModuleId),
+    /// Storage failed to load the module of the script function.
+    StorageError(ModuleId, String),
+    /// Module of the script function could not be deserialized.
+    InvalidModule(ModuleId),
+    /// Function does not exist or does not have script visibility.
+    FunctionNotFound(ModuleId, Identifier),
+    /// Number of type arguments does not match the number of type parameters.
+    TypeArgumentsCountMismatch { expected: usize, actual: usize },
+    /// Number of signers does not match the number of signer parameters.
+    SignersCountMismatch { expected: usize, actual: usize },
+    /// Number of arguments does not match the number of non-signer parameters.
+    ArgumentsCountMismatch { expected: usize, actual: usize },
+    /// Parameter at `position` can't be passed as a transaction argument.
+    UnsupportedParameter { position: usize },
+    /// Argument at `position` is not a valid bcs encoding of the parameter type.
+    InvalidArgument { position: usize, expected: TypeTag },
+}
+
+/// Function signature of a transaction entry point.
+pub(crate) struct EntrySignature {
+    version: u32,
+    type_parameters: usize,
+    parameters: Vec<SignatureToken>,
+}
+
+impl EntrySignature {
+    /// Loads signature of the script `main` function.
+    pub fn from_script(code: &[u8]) -> Result<EntrySignature, ValidationError> {
+        let script =
+            CompiledScript::deserialize(code).map_err(|_| ValidationError::InvalidScript)?;
+        Ok(EntrySignature {
+            version: script.version(),
+            type_parameters: script.type_parameters.len(),
+            parameters: script.signature_at(script.parameters).0.to_owned(),
+        })
+    }
+
+    /// Loads signature of the script function `func_name` of the given module.
+    pub fn from_module(
+        id: &ModuleId,
+        bytecode: &[u8],
+        func_name: &Identifier,
+    ) -> Result<EntrySignature, ValidationError> {
+        let module = CompiledModule::deserialize(bytecode)
+            .map_err(|_| ValidationError::InvalidModule(id.to_owned()))?;
+
+        module
+            .function_defs()
+            .iter()
+            .filter(|def| def.visibility == Visibility::Script)
+            .map(|def| module.function_handle_at(def.function))
+            .find(|handle| module.identifier_at(handle.name) == func_name.as_ident_str())
+            .map(|handle| EntrySignature {
+                version: module.version(),
+                type_parameters: handle.type_parameters.len(),
+                parameters: module.signature_at(handle.parameters).0.to_owned(),
+            })
+            .ok_or_else(|| ValidationError::FunctionNotFound(id.to_owned(), func_name.to_owned()))
+    }
+
+    /// Checks type arguments, signers and arguments of the transaction against the signature.
+    pub fn validate(
+        &self,
+        type_args: &[TypeTag],
+        signers: &[AccountAddress],
+        args: &[Vec<u8>],
+    ) -> Result<(), ValidationError> {
+        if self.type_parameters != type_args.len() {
+            return Err(ValidationError::TypeArgumentsCountMismatch {
+                expected: self.type_parameters,
+                actual: type_args.len(),
+            });
+        }
+
+        let signer_params = self
+            .parameters
+            .iter()
+            .take_while(|tok| self.is_signer(tok))
+            .count();
+        // Signers are either all passed or not used by the script at all.
+        if signer_params != 0 && signer_params != signers.len() {
+            return Err(ValidationError::SignersCountMismatch {
+                expected: signer_params,
+                actual: signers.len(),
+            });
+        }
+
+        let params = &self.parameters[signer_params..];
+        if params.len() != args.len() {
+            return Err(ValidationError::ArgumentsCountMismatch {
+                expected: params.len(),
+                actual: args.len(),
+            });
+        }
+
+        for (index, (param, arg)) in params.iter().zip(args).enumerate() {
+            let position = signer_params + index;
+            let expected = make_type_tag(param, type_args)
+                .ok_or(ValidationError::UnsupportedParameter { position })?;
+            let layout =
+                make_layout(&expected).ok_or(ValidationError::UnsupportedParameter { position })?;
+            if MoveValue::simple_deserialize(arg, &layout).is_err() {
+                return Err(ValidationError::InvalidArgument { position, expected });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn is_signer(&self, tok: &SignatureToken) -> bool {
+        if self.version <= VERSION_1 {
+            matches!(tok, SignatureToken::Reference(inner) if **inner == SignatureToken::Signer)
+        } else {
+            *tok == SignatureToken::Signer
+        }
+    }
+}
+
+/// Validates transaction against the signature of its entry point.
+///
+/// `load_module` is used to fetch the module bytecode of a script function call.
+pub(crate) fn validate_tx<F>(tx: &ScriptTx, load_module: F) -> Result<(), ValidationError>
+where
+    F: FnOnce(&ModuleId) -> Result<Option<Vec<u8>>, Error>,
+{
+    let signature = match tx.call() {
+        Call::Script { code } => EntrySignature::from_script(code)?,
+        Call::ScriptFunction {
+            mod_address,
+            mod_name,
+            func_name,
+        } => {
+            let id = ModuleId::new(*mod_address, mod_name.to_owned());
+            let bytecode = load_module(&id)
+                .map_err(|err| ValidationError::StorageError(id.clone(), err.to_string()))?
+                .ok_or_else(|| ValidationError::ModuleNotFound(id.clone()))?;
+            EntrySignature::from_module(&id, &bytecode, func_name)?
+        }
+    };
+    signature.validate(tx.type_parameters(), tx.signers(), tx.args())
+}
+
+fn make_type_tag(tok: &SignatureToken, type_args: &[TypeTag]) -> Option<TypeTag> {
+    Some(match tok {
+        SignatureToken::Bool => TypeTag::Bool,
+        SignatureToken::U8 => TypeTag::U8,
+        SignatureToken::U64 => TypeTag::U64,
+        SignatureToken::U128 => TypeTag::U128,
+        SignatureToken::Address => TypeTag::Address,
+        SignatureToken::Signer => TypeTag::Signer,
+        // `&signer` can be created from data with the layout of `signer`.
+        SignatureToken::Reference(inner) if **inner == SignatureToken::Signer => TypeTag::Signer,
+        SignatureToken::Vector(inner) => {
+            TypeTag::Vector(Box::new(make_type_tag(inner, type_args)?))
+        }
+        SignatureToken::TypeParameter(idx) => type_args.get(*idx as usize)?.to_owned(),
+        SignatureToken::Struct(_)
+        | SignatureToken::StructInstantiation(_, _)
+        | SignatureToken::Reference(_)
+        | SignatureToken::MutableReference(_) => return None,
+    })
+}
+
+fn make_layout(tag: &TypeTag) -> Option<MoveTypeLayout> {
+    Some(match tag {
+        TypeTag::Bool => MoveTypeLayout::Bool,
+        TypeTag::U8 => MoveTypeLayout::U8,
+        TypeTag::U64 => MoveTypeLayout::U64,
+        TypeTag::U128 => MoveTypeLayout::U128,
+        TypeTag::Address => MoveTypeLayout::Address,
+        TypeTag::Signer => MoveTypeLayout::Signer,
+        TypeTag::Vector(inner) => MoveTypeLayout::Vector(Box::new(make_layout(inner)?)),
+        TypeTag::Struct(_) => return None,
+    })
+}
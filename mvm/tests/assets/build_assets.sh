@@ -31,8 +31,8 @@ set -e
 dove build
 dove tx "store_u64(13)"
 dove tx "tx_test<0x01::Pontem::T>(100)"
-dove build -b -o "valid_pack"  --modules_exclude "ReflectTest"
-dove build -b -o "invalid_pack" --modules_exclude "Store" "ReflectTest"
+dove build -b -o "valid_pack"  --modules_exclude "ReflectTest" "Host" "HostMath"
+dove build -b -o "invalid_pack" --modules_exclude "Store" "ReflectTest" "Host" "HostMath"
 
 dove tx "rt_signers(rt)"
 dove tx "signers_tr_with_user(root)"
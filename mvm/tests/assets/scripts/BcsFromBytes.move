@@ -0,0 +1,8 @@
+script {
+    use Std::BCS;
+
+    fun bcs_from_bytes<T: copy + drop>(bytes: vector<u8>) {
+        let value = BCS::from_bytes<T>(copy bytes);
+        assert!(BCS::to_bytes(&value) == bytes, 1);
+    }
+}
@@ -0,0 +1,13 @@
+script {
+    use PontemFramework::PontBlock;
+    use PontemFramework::PontChainId;
+    use PontemFramework::PontTransaction;
+    use Std::Signer;
+
+    fun context_resources(account: signer, parent_block_hash: vector<u8>, tx_hash: vector<u8>, chain_id: u8) {
+        assert!(PontBlock::get_parent_block_hash() == parent_block_hash, 1);
+        assert!(PontTransaction::get_transaction_hash() == tx_hash, 2);
+        assert!(PontTransaction::get_transaction_sender() == Signer::address_of(&account), 3);
+        assert!(PontChainId::get_chain_id() == chain_id, 4);
+    }
+}
@@ -0,0 +1,8 @@
+script {
+    use Std::Debug;
+
+    fun debug_print(val: u64) {
+        Debug::print(&val);
+        Debug::print_stack_trace();
+    }
+}
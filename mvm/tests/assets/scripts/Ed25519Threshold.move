@@ -0,0 +1,7 @@
+script {
+    use Std::Signature;
+
+    fun ed25519_threshold(signature: vector<u8>, public_key: vector<u8>, message: vector<u8>, valid: bool) {
+        assert!(Signature::ed25519_threshold_verify(signature, public_key, message) == valid, 1);
+    }
+}
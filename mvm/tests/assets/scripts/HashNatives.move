@@ -0,0 +1,8 @@
+script {
+    use Std::Hash;
+
+    fun hash_natives(data: vector<u8>, keccak256: vector<u8>, blake2b_256: vector<u8>) {
+        assert!(Hash::keccak256(copy data) == keccak256, 1);
+        assert!(Hash::blake2b_256(data) == blake2b_256, 2);
+    }
+}
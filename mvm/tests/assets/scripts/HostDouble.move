@@ -0,0 +1,7 @@
+script {
+    use Assets::HostMath;
+
+    fun host_double(val: u64, expected: u64) {
+        assert!(HostMath::double(val) == expected, 1);
+    }
+}
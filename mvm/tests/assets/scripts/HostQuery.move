@@ -0,0 +1,7 @@
+script {
+    use Assets::Host;
+
+    fun host_query(key: vector<u8>, expected: vector<u8>) {
+        assert!(Host::query(key) == expected, 1);
+    }
+}
@@ -0,0 +1,8 @@
+script {
+    use Std::Reflect;
+
+    fun reflect_type_name<T1, T2>(expected_name: vector<u8>, same_type: bool) {
+        assert!(Reflect::type_name<T1>() == expected_name, 1);
+        assert!(Reflect::is_same_type<T1, T2>() == same_type, 2);
+    }
+}
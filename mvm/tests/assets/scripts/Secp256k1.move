@@ -0,0 +1,21 @@
+script {
+    use Std::Signature;
+    use Std::Vector;
+
+    fun secp256k1(
+        signature: vector<u8>,
+        msg_hash: vector<u8>,
+        expected_pubkey: vector<u8>,
+        recovered: bool,
+        verified: bool,
+    ) {
+        let (pubkey, ok) = Signature::secp256k1_recover(copy signature, copy msg_hash);
+        assert!(ok == recovered, 1);
+        if (recovered) {
+            assert!(pubkey == expected_pubkey, 2);
+        } else {
+            assert!(Vector::is_empty(&pubkey), 3);
+        };
+        assert!(Signature::secp256k1_verify(signature, expected_pubkey, msg_hash) == verified, 4);
+    }
+}
@@ -0,0 +1,20 @@
+script {
+    use Std::U256;
+
+    fun u256_failure(case: u8) {
+        let max = U256::from_be_bytes(x"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let zero = U256::from_u8(0);
+
+        let _ = if (case == 0) {
+            U256::mod(copy max, zero)
+        } else if (case == 1) {
+            U256::pow(max, U256::from_u8(2))
+        } else if (case == 2) {
+            U256::mul_div(max, U256::from_u8(2), U256::from_u8(1))
+        } else if (case == 3) {
+            U256::mul_div(max, U256::from_u8(2), zero)
+        } else {
+            U256::from_le_bytes(x"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff00")
+        };
+    }
+}
@@ -0,0 +1,27 @@
+script {
+    use Std::U256;
+
+    fun u256_natives() {
+        let max = U256::from_be_bytes(x"ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff");
+        let one = U256::from_u8(1);
+
+        assert!(U256::mod(U256::from_u64(10), U256::from_u64(3)) == copy one, 1);
+        assert!(U256::pow(U256::from_u8(2), U256::from_u8(10)) == U256::from_u64(1024), 2);
+        // The intermediate product does not fit into 256 bits.
+        assert!(U256::mul_div(copy max, U256::from_u8(3), U256::from_u8(6)) == U256::shr(copy max, 1), 3);
+
+        assert!(U256::shl(copy one, 255) == U256::xor(copy max, U256::shr(copy max, 1)), 4);
+        assert!(U256::and(copy max, U256::from_u128(7)) == U256::from_u8(7), 5);
+        assert!(U256::or(U256::from_u8(5), U256::from_u8(2)) == U256::from_u8(7), 6);
+
+        assert!(U256::lt(copy one, copy max) && !U256::lt(copy max, copy max), 7);
+        assert!(U256::le(copy one, copy max) && U256::le(copy max, copy max), 8);
+        assert!(U256::gt(copy max, copy one) && !U256::gt(copy max, copy max), 9);
+        assert!(U256::ge(copy max, copy one) && U256::ge(copy max, copy max), 10);
+
+        assert!(U256::from_le_bytes(x"0102") == U256::from_u64(513), 11);
+        assert!(U256::from_be_bytes(x"0102") == U256::from_u64(258), 12);
+        assert!(U256::from_be_bytes(U256::to_be_bytes(U256::from_u64(258))) == U256::from_u64(258), 13);
+        assert!(U256::from_le_bytes(U256::to_le_bytes(copy max)) == max, 14);
+    }
+}
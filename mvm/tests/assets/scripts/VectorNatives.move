@@ -0,0 +1,26 @@
+script {
+    use Std::Vector;
+
+    fun vector_natives() {
+        let v = Vector::empty<u64>();
+        Vector::push_back(&mut v, 1);
+        Vector::push_back(&mut v, 2);
+        let other = Vector::singleton(3);
+        Vector::push_back(&mut other, 4);
+
+        Vector::append(&mut v, other);
+        assert!(Vector::length(&v) == 4, 1);
+        Vector::reverse(&mut v);
+        assert!(*Vector::borrow(&v, 0) == 4 && *Vector::borrow(&v, 3) == 1, 2);
+
+        assert!(Vector::contains(&v, &3), 3);
+        assert!(!Vector::contains(&v, &5), 4);
+        let (found, index) = Vector::index_of(&v, &2);
+        assert!(found && index == 2, 5);
+        let (found, _) = Vector::index_of(&v, &5);
+        assert!(!found, 6);
+
+        assert!(Vector::remove(&mut v, 1) == 3, 7);
+        assert!(Vector::length(&v) == 3 && *Vector::borrow(&v, 1) == 2, 8);
+    }
+}
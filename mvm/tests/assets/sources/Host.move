@@ -0,0 +1,3 @@
+module Assets::Host {
+    native public fun query(key: vector<u8>): vector<u8>;
+}
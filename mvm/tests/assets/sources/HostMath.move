@@ -0,0 +1,3 @@
+module Assets::HostMath {
+    native public fun double(val: u64): u64;
+}
@@ -1,9 +1,241 @@
+use std::borrow::Cow;
+use std::fmt::Debug;
+
+use move_binary_format::errors::Location;
+use move_core_types::account_address::AccountAddress;
 use move_core_types::identifier::Identifier;
+use move_core_types::language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS};
+use move_core_types::vm_status::{AbortLocation, StatusCode, VMStatus};
+use mvm::error::{Category, SubStatus};
+use mvm::io::balance::{BalanceOp, CurrencyInfo};
+use mvm::io::context::ExecutionContext;
+use mvm::io::traits::TxEvent;
+use mvm::types::{
+    Call, ErrorReport, Gas, ModulePackage, ModuleTx, ScriptArg, ScriptTx, Signer, StackFrame,
+    Transaction, TxV1, VmResult, VmStatusEvent,
+};
 use parity_scale_codec::{Decode, Encode};
 
+fn round_trip<T: Encode + Decode + PartialEq + Debug>(value: T) {
+    let buffer = value.encode();
+    assert_eq!(value, T::decode(&mut buffer.as_ref()).unwrap());
+}
+
+fn ident(name: &str) -> Identifier {
+    Identifier::new(name).unwrap()
+}
+
+fn module_id() -> ModuleId {
+    ModuleId::new(CORE_CODE_ADDRESS, ident("Store"))
+}
+
+fn type_tag() -> TypeTag {
+    TypeTag::Struct(StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: ident("PONT"),
+        name: ident("T"),
+        type_params: vec![TypeTag::U64],
+    })
+}
+
 #[test]
 pub fn test_identifier() {
     let ident = Identifier::new("Test_Ident").unwrap();
     let buffer = ident.encode();
     assert_eq!(ident, Identifier::decode(&mut buffer.as_ref()).unwrap())
 }
+
+#[test]
+pub fn test_status_codes() {
+    round_trip(StatusCode::EXECUTED);
+    round_trip(StatusCode::STORAGE_ERROR);
+    assert_eq!(StatusCode::ABORTED.encode(), 4016u64.to_le_bytes().to_vec());
+    round_trip(StatusCode::UNKNOWN_STATUS);
+    assert!(StatusCode::decode(&mut 4999u64.encode().as_ref()).is_err());
+
+    round_trip(VMStatus::Executed);
+    round_trip(VMStatus::Error(StatusCode::OUT_OF_GAS));
+    round_trip(VMStatus::MoveAbort(AbortLocation::Script, 7));
+    round_trip(VMStatus::ExecutionFailure {
+        status_code: StatusCode::ARITHMETIC_ERROR,
+        location: AbortLocation::Module(module_id()),
+        function: 1,
+        code_offset: 2,
+    });
+    assert_eq!(VMStatus::Executed.encode(), vec![0]);
+    assert_eq!(AbortLocation::Script.encode(), vec![1]);
+
+    round_trip(Location::Undefined);
+    round_trip(Location::Script);
+    round_trip(Location::Module(module_id()));
+    assert_eq!(Location::Script.encode(), vec![1]);
+}
+
+#[test]
+pub fn test_sub_status() {
+    assert_eq!(Category::INVALID_STATE.encode(), vec![1]);
+    assert_eq!(Category::LIMIT_EXCEEDED.encode(), vec![8]);
+    assert_eq!(Category::INTERNAL.encode(), vec![10]);
+    assert_eq!(Category::CUSTOM.encode(), vec![255]);
+    assert!(Category::decode(&mut [9u8].as_ref()).is_err());
+
+    for code in &[0x0105, 0x0207, 0x000a, 0x42ff] {
+        let sub_status = SubStatus::new(*code);
+        assert_eq!(sub_status.encode()[0], *code as u8);
+        round_trip(sub_status);
+    }
+}
+
+#[test]
+pub fn test_vm_result() {
+    let frame = StackFrame {
+        module: Some(module_id()),
+        function: Some(ident("store_u64")),
+        function_index: 3,
+        code_offset: 12,
+    };
+    round_trip(VmResult {
+        status_code: StatusCode::ABORTED,
+        sub_status: Some(SubStatus::new(0x0107)),
+        gas_used: 1000,
+        gas_refund: 100,
+        gas_schedule_version: 2,
+        location: Some(Location::Module(module_id())),
+        balance_ops: vec![
+            BalanceOp::Sub(CORE_CODE_ADDRESS, Cow::Borrowed(b"PONT"), 10),
+            BalanceOp::Add(AccountAddress::random(), Cow::Owned(b"PONT".to_vec()), 10),
+        ],
+        error_report: Some(ErrorReport {
+            frame: Some(frame.clone()),
+            stack_trace: vec![
+                StackFrame {
+                    module: None,
+                    function: None,
+                    function_index: 0,
+                    code_offset: 4,
+                },
+                frame,
+            ],
+            message: Some("abort".to_owned()),
+        }),
+    });
+    round_trip(VmResult {
+        status_code: StatusCode::EXECUTED,
+        sub_status: None,
+        gas_used: 0,
+        gas_refund: 0,
+        gas_schedule_version: 1,
+        location: None,
+        balance_ops: vec![],
+        error_report: None,
+    });
+
+    round_trip(VmStatusEvent {
+        tx_index: 1,
+        sender: AccountAddress::random(),
+        status: VMStatus::MoveAbort(AbortLocation::Module(module_id()), 0x0107),
+        sub_status: Some(0x0107),
+        location: Some(AbortLocation::Module(module_id())),
+        gas_used: 42,
+    });
+}
+
+#[test]
+pub fn test_balance_types() {
+    let op = BalanceOp::Add(CORE_CODE_ADDRESS, Cow::Borrowed(b"PONT"), u128::MAX);
+    assert_eq!(op.encode()[0], 1);
+    round_trip(op);
+    let op = BalanceOp::Sub(CORE_CODE_ADDRESS, Cow::Borrowed(b"PONT"), 1);
+    assert_eq!(op.encode()[0], 0);
+    round_trip(op);
+
+    round_trip(CurrencyInfo { total_value: 1000 });
+}
+
+#[test]
+pub fn test_transactions() {
+    round_trip(Gas::new(10_000, 1).unwrap());
+    round_trip(Gas::infinite());
+    let mut buffer = Gas::infinite().encode();
+    buffer[0] = 0xff;
+    assert!(Gas::decode(&mut buffer.as_ref()).is_err());
+
+    round_trip(ModuleTx::new(
+        vec![0xa1, 0x1c, 0xeb, 0x0b],
+        CORE_CODE_ADDRESS,
+    ));
+    round_trip(ModulePackage::default().into_tx(CORE_CODE_ADDRESS));
+
+    round_trip(
+        ScriptTx::with_script(
+            vec![0xa1, 0x1c, 0xeb, 0x0b],
+            vec![
+                ScriptArg::U8(1),
+                ScriptArg::U64(2),
+                ScriptArg::U128(3),
+                ScriptArg::Bool(true),
+                ScriptArg::Address(CORE_CODE_ADDRESS),
+                ScriptArg::VectorU8(vec![1, 2]),
+            ],
+            vec![type_tag()],
+            vec![AccountAddress::random()],
+        )
+        .unwrap(),
+    );
+
+    let arg = ScriptArg::VectorAddress(vec![CORE_CODE_ADDRESS]);
+    assert_eq!(arg.encode()[0], 9);
+    round_trip(arg);
+    round_trip(ScriptArg::VectorU64(vec![1, 2]));
+    round_trip(ScriptArg::VectorU128(vec![1, 2]));
+    round_trip(ScriptArg::VectorBool(vec![true, false]));
+
+    let script = Call::Script {
+        code: vec![0xa1, 0x1c, 0xeb, 0x0b],
+    };
+    assert_eq!(script.encode()[0], 0);
+    let function = Call::ScriptFunction {
+        mod_address: CORE_CODE_ADDRESS,
+        mod_name: ident("Store"),
+        func_name: ident("store_u64"),
+    };
+    assert_eq!(function.encode()[0], 1);
+
+    assert_eq!(Signer::Root.encode(), vec![0]);
+    assert_eq!(Signer::Placeholder.encode(), vec![1]);
+    let calls: Vec<_> = vec![script, function];
+    for call in calls {
+        let tx = Transaction::V1(TxV1 {
+            signers: vec![
+                Signer::Root,
+                Signer::Placeholder,
+                Signer::Name("Alice".to_owned()),
+            ],
+            call,
+            args: vec![vec![1], vec![2, 3]],
+            type_args: vec![type_tag()],
+        });
+        assert_eq!(tx.encode()[0], 0);
+        round_trip(tx);
+    }
+}
+
+#[test]
+pub fn test_host_types() {
+    round_trip(ExecutionContext::new(100, 1));
+    round_trip(ExecutionContext {
+        sender: Some(AccountAddress::random()),
+        ..ExecutionContext::new(100, 1)
+            .with_parent_block_hash(vec![1; 32])
+            .with_tx_hash(vec![2; 32])
+            .with_chain_id(42)
+    });
+
+    round_trip(TxEvent {
+        index: 2,
+        guid: vec![1, 2, 3],
+        seq_num: 4,
+        ty_tag: type_tag(),
+        message: vec![5, 6],
+    });
+}
@@ -48,6 +48,13 @@ pub fn abort_module() -> ModuleTx {
     )
 }
 
+pub fn host_module() -> ModuleTx {
+    ModuleTx::new(
+        include_bytes!("../assets/build/assets/bytecode_modules/Host.mv").to_vec(),
+        CORE_CODE_ADDRESS,
+    )
+}
+
 pub fn emit_event_script(addr: AccountAddress, args: u64) -> ScriptTx {
     ScriptTx::with_script(
         include_bytes!("../assets/build/assets/bytecode_scripts/emit_event.mv").to_vec(),
@@ -72,6 +79,25 @@ pub fn store_sys_resources_script(
     .unwrap()
 }
 
+pub fn context_resources_script(
+    sender: AccountAddress,
+    parent_block_hash: &[u8],
+    tx_hash: &[u8],
+    chain_id: u8,
+) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/context_resources.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(parent_block_hash.to_vec()),
+            ScriptArg::VectorU8(tx_hash.to_vec()),
+            ScriptArg::U8(chain_id),
+        ],
+        vec![],
+        vec![sender],
+    )
+    .unwrap()
+}
+
 pub fn store_u64_script(addr: AccountAddress, args: u64) -> ScriptTx {
     ScriptTx::with_script(
         include_bytes!("../assets/build/assets/bytecode_scripts/store_u64.mv").to_vec(),
@@ -122,9 +148,9 @@ pub fn transfer_script(
     ScriptTx::with_script(
         include_bytes!("../assets/build/assets/bytecode_scripts/transfer.mv").to_vec(),
         vec![
-            ScriptArg::U64(from_balance),
-            ScriptArg::U64(to_balance),
-            ScriptArg::U64(to_move),
+            ScriptArg::U64(from_balance as u64),
+            ScriptArg::U64(to_balance as u64),
+            ScriptArg::U64(to_move as u64),
         ],
         vec![],
         vec![from, to],
@@ -191,6 +217,160 @@ pub fn reflect_type_of(addr: AccountAddress, module: &str, strct: &str) -> Scrip
     .unwrap()
 }
 
+pub fn host_math_module() -> ModuleTx {
+    ModuleTx::new(
+        include_bytes!("../assets/build/assets/bytecode_modules/HostMath.mv").to_vec(),
+        CORE_CODE_ADDRESS,
+    )
+}
+
+pub fn host_double_script(val: u64, expected: u64) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/host_double.mv").to_vec(),
+        vec![ScriptArg::U64(val), ScriptArg::U64(expected)],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn host_query_script(key: &[u8], expected: &[u8]) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/host_query.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(key.to_vec()),
+            ScriptArg::VectorU8(expected.to_vec()),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn debug_print_script(val: u64) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/debug_print.mv").to_vec(),
+        vec![ScriptArg::U64(val)],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn vector_natives_script() -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/vector_natives.mv").to_vec(),
+        vec![],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn u256_natives_script() -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/u256_natives.mv").to_vec(),
+        vec![],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn u256_failure_script(case: u8) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/u256_failure.mv").to_vec(),
+        vec![ScriptArg::U8(case)],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn bcs_from_bytes_script(ty: TypeTag, bytes: Vec<u8>) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/bcs_from_bytes.mv").to_vec(),
+        vec![ScriptArg::VectorU8(bytes)],
+        vec![ty],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn reflect_type_name_script(
+    ty: TypeTag,
+    other: TypeTag,
+    expected_name: &str,
+    same_type: bool,
+) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/reflect_type_name.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(expected_name.as_bytes().to_vec()),
+            ScriptArg::Bool(same_type),
+        ],
+        vec![ty, other],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn ed25519_threshold_script(
+    signature: &[u8],
+    public_key: &[u8],
+    message: &[u8],
+    valid: bool,
+) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/ed25519_threshold.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(signature.to_vec()),
+            ScriptArg::VectorU8(public_key.to_vec()),
+            ScriptArg::VectorU8(message.to_vec()),
+            ScriptArg::Bool(valid),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn hash_natives_script(data: &[u8], keccak256: &[u8], blake2b_256: &[u8]) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/hash_natives.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(data.to_vec()),
+            ScriptArg::VectorU8(keccak256.to_vec()),
+            ScriptArg::VectorU8(blake2b_256.to_vec()),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
+pub fn secp256k1_script(
+    signature: &[u8],
+    msg_hash: &[u8],
+    expected_pubkey: &[u8],
+    recovered: bool,
+    verified: bool,
+) -> ScriptTx {
+    ScriptTx::with_script(
+        include_bytes!("../assets/build/assets/bytecode_scripts/secp256k1.mv").to_vec(),
+        vec![
+            ScriptArg::VectorU8(signature.to_vec()),
+            ScriptArg::VectorU8(msg_hash.to_vec()),
+            ScriptArg::VectorU8(expected_pubkey.to_vec()),
+            ScriptArg::Bool(recovered),
+            ScriptArg::Bool(verified),
+        ],
+        vec![],
+        vec![],
+    )
+    .unwrap()
+}
+
 pub fn valid_package() -> ModulePackage {
     ModulePackage::try_from(&include_bytes!("../assets/build/assets/bundles/valid_pack.pac")[..])
         .unwrap()
@@ -1,13 +1,16 @@
 use std::borrow::Cow;
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
+use anyhow::{ensure, Error};
 use move_core_types::account_address::AccountAddress;
 use move_core_types::effects::Event;
 use move_core_types::language_storage::TypeTag;
 use move_core_types::vm_status::StatusCode;
-use mvm::io::traits::{Balance, BalanceAccess, CurrencyAccessPath, EventHandler, Storage};
+use mvm::io::traits::{
+    Balance, BalanceAccess, CurrencyAccessPath, EventHandler, FallibleStorage, Storage, TxEvent,
+};
 use mvm::mvm::Mvm;
 use mvm::types::{ModuleTx, ScriptTx};
 use mvm::Vm;
@@ -52,9 +55,54 @@ impl Storage for StorageMock {
     }
 }
 
+/// Storage with a failing backend.
+#[derive(Clone, Default)]
+pub struct FailingStorageMock {
+    pub inner: StorageMock,
+    pub fail_reads: Rc<Cell<bool>>,
+    pub fail_writes: Rc<Cell<bool>>,
+    /// Number of the inserts to accept before a single failure, if set.
+    pub inserts_left: Rc<Cell<Option<usize>>>,
+}
+
+impl FailingStorageMock {
+    pub fn new(inner: StorageMock) -> FailingStorageMock {
+        FailingStorageMock {
+            inner,
+            fail_reads: Default::default(),
+            fail_writes: Default::default(),
+            inserts_left: Default::default(),
+        }
+    }
+}
+
+impl FallibleStorage for FailingStorageMock {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        ensure!(!self.fail_reads.get(), "Failed to read {:?}", key);
+        Ok(Storage::get(&self.inner, key))
+    }
+
+    fn insert(&self, key: &[u8], value: &[u8]) -> Result<(), Error> {
+        ensure!(!self.fail_writes.get(), "Failed to write {:?}", key);
+        if let Some(left) = self.inserts_left.get() {
+            self.inserts_left.set(left.checked_sub(1));
+            ensure!(left > 0, "Failed to write {:?}", key);
+        }
+        Storage::insert(&self.inner, key, value);
+        Ok(())
+    }
+
+    fn remove(&self, key: &[u8]) -> Result<(), Error> {
+        ensure!(!self.fail_writes.get(), "Failed to remove {:?}", key);
+        Storage::remove(&self.inner, key);
+        Ok(())
+    }
+}
+
 #[derive(Clone, Default)]
 pub struct EventHandlerMock {
     pub data: Rc<RefCell<Vec<Event>>>,
+    pub batches: Rc<RefCell<Vec<(u64, Vec<TxEvent>)>>>,
 }
 
 impl EventHandlerMock {
@@ -68,6 +116,13 @@ impl EventHandler for EventHandlerMock {
         let mut data = self.data.borrow_mut();
         data.push((guid, seq_num, ty_tag, message));
     }
+
+    fn on_tx_events(&self, tx_index: u64, events: Vec<TxEvent>) {
+        self.batches.borrow_mut().push((tx_index, events.clone()));
+        for event in events {
+            self.on_event(event.guid, event.seq_num, event.ty_tag, event.message);
+        }
+    }
 }
 
 #[derive(Clone, Debug, Default)]
@@ -75,6 +130,9 @@ pub struct BankMock {
     reserved_balance: Rc<RefCell<Balance>>,
     currency_info: Rc<RefCell<HashMap<Cow<'static, [u8]>, CurrencyInfo>>>,
     balances: Rc<RefCell<HashMap<AccountAddress, HashMap<Cow<'static, [u8]>, Balance>>>>,
+    locked: Rc<RefCell<HashSet<AccountAddress>>>,
+    /// Number of the balance operations to accept before rejecting all the following ones, if set.
+    pub ops_left: Rc<Cell<Option<usize>>>,
 }
 
 impl BankMock {
@@ -93,6 +151,24 @@ impl BankMock {
         let acc = acc_map.entry(*address).or_insert_with(HashMap::new);
         *acc.entry(Cow::Owned(path.to_vec())).or_insert(amount) = amount;
     }
+
+    /// Rejects the balance operations of the `address`.
+    pub fn lock(&self, address: &AccountAddress) {
+        self.locked.borrow_mut().insert(*address);
+    }
+
+    fn ensure_unlocked(&self, address: &AccountAddress) -> Result<(), Error> {
+        ensure!(
+            !self.locked.borrow().contains(address),
+            "Account {} is locked",
+            address
+        );
+        if let Some(left) = self.ops_left.get() {
+            ensure!(left > 0, "Bank is closed");
+            self.ops_left.set(Some(left - 1));
+        }
+        Ok(())
+    }
 }
 
 impl BalanceAccess for BankMock {
@@ -108,35 +184,53 @@ impl BalanceAccess for BankMock {
             .and_then(|acc| acc.get(path).cloned())
     }
 
-    fn sub(&self, address: &AccountAddress, path: &CurrencyAccessPath, amount: Balance) {
-        *self.reserved_balance.borrow_mut() += amount;
+    fn sub(
+        &self,
+        address: &AccountAddress,
+        path: &CurrencyAccessPath,
+        amount: Balance,
+    ) -> Result<(), Error> {
+        self.ensure_unlocked(address)?;
 
         let mut acc_map = self.balances.borrow_mut();
         let acc = acc_map.entry(*address).or_insert_with(HashMap::new);
         let val = acc.entry(Cow::Owned(path.to_vec())).or_insert(0);
-        if *val < amount {
-            panic!(
-                "Not enough currency in the account [{}::{:?}] You need {} units in stock {}",
-                address, path, amount, val
-            );
-        }
+        ensure!(
+            *val >= amount,
+            "Not enough currency in the account [{}::{:?}] You need {} units in stock {}",
+            address,
+            path,
+            amount,
+            val
+        );
         *val -= amount;
+        *self.reserved_balance.borrow_mut() += amount;
+        Ok(())
     }
 
-    fn add(&self, address: &AccountAddress, path: &CurrencyAccessPath, amount: Balance) {
-        let mut reserved_balance = self.reserved_balance.borrow_mut();
+    fn add(
+        &self,
+        address: &AccountAddress,
+        path: &CurrencyAccessPath,
+        amount: Balance,
+    ) -> Result<(), Error> {
+        self.ensure_unlocked(address)?;
 
-        if *reserved_balance < amount {
-            panic!(
-                "Not enough currency reserved in bank to withdraw to account {}::{:?}] You need {} reserved in bank, current balance {}",
-                address, path, amount, reserved_balance,
-            )
-        }
+        let mut reserved_balance = self.reserved_balance.borrow_mut();
+        ensure!(
+            *reserved_balance >= amount,
+            "Not enough currency reserved in bank to withdraw to account {}::{:?}] You need {} reserved in bank, current balance {}",
+            address,
+            path,
+            amount,
+            reserved_balance
+        );
         let mut acc_map = self.balances.borrow_mut();
         let acc = acc_map.entry(*address).or_insert_with(HashMap::new);
         let val = acc.entry(Cow::Owned(path.to_vec())).or_insert(0);
         *val += amount;
         *reserved_balance -= amount;
+        Ok(())
     }
 }
 
@@ -152,12 +246,12 @@ pub trait Utils {
 
 impl<S, E, B> Utils for Mvm<S, E, B>
 where
-    S: Storage,
+    S: FallibleStorage,
     E: EventHandler,
     B: BalanceAccess,
 {
     fn pub_mod(&self, module: ModuleTx) {
-        let res = self.publish_module(gas(), module, false);
+        let res = self.publish_module(gas(), 0, module, false);
         if res.status_code != StatusCode::EXECUTED {
             panic!("Transaction failed: {:?}", res);
         }
@@ -1,12 +1,16 @@
 #![allow(dead_code)]
 
 use diem_types::account_config::CORE_CODE_ADDRESS;
+use move_binary_format::access::ModuleAccess;
+use move_binary_format::CompiledModule;
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::ModuleId;
 use move_core_types::resolver::ModuleResolver;
+use mvm::gas_schedule::GasSchedules;
 use mvm::genesis::init_storage;
 use mvm::io::state::State;
 use mvm::mvm::Mvm;
+use mvm::natives::HostNatives;
 
 use crate::common::mock::{BankMock, EventHandlerMock, StorageMock};
 
@@ -28,6 +32,21 @@ pub fn vm() -> (
     (vm, store, event, bank)
 }
 
+pub fn vm_with_gas_schedules(
+    schedules: GasSchedules,
+) -> Mvm<StorageMock, EventHandlerMock, BankMock> {
+    let store = StorageMock::new();
+    init_storage(store.clone(), Default::default()).unwrap();
+    Mvm::new_with_gas_schedules(
+        store,
+        EventHandlerMock::default(),
+        BankMock::default(),
+        HostNatives::default(),
+        schedules,
+    )
+    .unwrap()
+}
+
 pub fn contains_core_module(state: &State<StorageMock>, name: &str) {
     if state
         .get_module(&ModuleId::new(
@@ -40,3 +59,23 @@ pub fn contains_core_module(state: &State<StorageMock>, name: &str) {
         panic!("Module {} not found", name);
     }
 }
+
+pub fn contains_native_functions(state: &State<StorageMock>, module: &str, functions: &[&str]) {
+    let id = ModuleId::new(CORE_CODE_ADDRESS, Identifier::new(module).unwrap());
+    let module_bytes = state
+        .get_module(&id)
+        .unwrap()
+        .unwrap_or_else(|| panic!("Module {} not found", module));
+    let compiled = CompiledModule::deserialize(&module_bytes).unwrap();
+    for name in functions {
+        let def = compiled
+            .function_defs()
+            .iter()
+            .find(|def| {
+                let handle = compiled.function_handle_at(def.function);
+                compiled.identifier_at(handle.name).as_str() == *name
+            })
+            .unwrap_or_else(|| panic!("Function {}::{} not found", module, name));
+        assert!(def.is_native(), "{}::{} is not native", module, name);
+    }
+}
@@ -0,0 +1,114 @@
+use move_core_types::gas_schedule::{GasAlgebra, GasConstants};
+use mvm::gas_schedule::{
+    cost_table, per_byte_gas_schedule, GasScheduleVersion, GasSchedules, StorageRefund,
+    WriteSetCharging, WriteSetCosts, WriteSetSize, DEFAULT_GAS_SCHEDULE_VERSION,
+    PER_BYTE_GAS_SCHEDULE_VERSION,
+};
+
+#[test]
+fn test_write_set_size() {
+    let mut size = WriteSetSize::default();
+    // New entry.
+    size.add_write(None, 10);
+    // Overwritten entry growing by 4 bytes.
+    size.add_write(Some(6), 10);
+    // Overwritten entry shrinking by 4 bytes.
+    size.add_write(Some(10), 6);
+    size.add_event(8);
+    assert_eq!(
+        size,
+        WriteSetSize {
+            new_bytes: 14,
+            overwritten_bytes: 12,
+            event_bytes: 8,
+            deleted_bytes: 0,
+        }
+    );
+
+    let costs = WriteSetCosts::default();
+    assert_eq!(
+        size.cost(&costs).get(),
+        14 * costs.write_cost.get() + 12 * costs.overwrite_cost.get() + 8 * costs.event_cost.get()
+    );
+    assert_eq!(WriteSetSize::default().cost(&costs).get(), 0);
+}
+
+#[test]
+fn test_refund() {
+    let constants = GasConstants::default();
+    let policy = StorageRefund::default();
+    let mut size = WriteSetSize::default();
+    assert_eq!(size.refund(&policy, &constants, 1000), 0);
+
+    size.add_delete(Some(5000));
+    // Missing resource frees nothing.
+    size.add_delete(None);
+    assert_eq!(size.deleted_bytes, 5000);
+
+    let refund = 5000 * policy.per_byte.get() / constants.gas_unit_scaling_factor;
+    assert!(refund > 0);
+    assert_eq!(size.refund(&policy, &constants, u64::MAX), refund);
+
+    // Capped at the share of the gas used.
+    let gas_used = refund;
+    let cap = gas_used * policy.max_percent / 100;
+    assert!(cap < refund);
+    assert_eq!(size.refund(&policy, &constants, gas_used), cap);
+    assert_eq!(size.refund(&policy, &constants, 0), 0);
+}
+
+#[test]
+fn test_gas_schedules() {
+    let schedules = GasSchedules::default();
+    let schedule = schedules.at_height(0);
+    assert_eq!(schedule.version, DEFAULT_GAS_SCHEDULE_VERSION);
+    assert_eq!(schedule.write_set_charging, WriteSetCharging::PerAccount);
+    assert_eq!(schedule.storage_refund, None);
+    assert_eq!(schedules.at_height(u64::MAX).cost_table, cost_table());
+
+    let schedules = GasSchedules::new(GasScheduleVersion::new(1, cost_table()))
+        .with_version(200, GasScheduleVersion::new(3, cost_table()))
+        .unwrap()
+        .with_version(100, GasScheduleVersion::new(2, cost_table()))
+        .unwrap();
+    let versions = [(0, 1), (99, 1), (100, 2), (199, 2), (200, 3), (u64::MAX, 3)];
+    for &(height, version) in &versions {
+        let schedule = schedules.at_height(height);
+        assert_eq!(schedule.version, version);
+        assert!(schedule.activation_height <= height);
+    }
+
+    assert!(schedules
+        .clone()
+        .with_version(300, GasScheduleVersion::new(2, cost_table()))
+        .is_err());
+    assert!(schedules
+        .clone()
+        .with_version(100, GasScheduleVersion::new(4, cost_table()))
+        .is_err());
+    assert!(schedules
+        .with_version(0, GasScheduleVersion::new(4, cost_table()))
+        .is_err());
+}
+
+#[test]
+fn test_gas_refund() {
+    let mut size = WriteSetSize::default();
+    size.add_delete(Some(5000));
+
+    let legacy = GasScheduleVersion::new(DEFAULT_GAS_SCHEDULE_VERSION, cost_table());
+    assert_eq!(legacy.gas_refund(&size, u64::MAX), 0);
+
+    let schedule = per_byte_gas_schedule();
+    assert_eq!(schedule.version, PER_BYTE_GAS_SCHEDULE_VERSION);
+    assert_eq!(
+        schedule.write_set_charging,
+        WriteSetCharging::PerByte(WriteSetCosts::default())
+    );
+    let policy = StorageRefund::default();
+    assert_eq!(
+        schedule.gas_refund(&size, u64::MAX),
+        size.refund(&policy, &schedule.cost_table.gas_constants, u64::MAX)
+    );
+    assert!(schedule.gas_refund(&size, u64::MAX) > 0);
+}
@@ -8,6 +8,8 @@ use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS};
 use move_core_types::value::MoveValue;
 use mvm::abi::{Field, Func, ModuleAbi, StructDef, Type, TypeAbilities};
+use mvm::io::balance::BalanceWidth;
+use mvm::io::traits::Balance;
 use mvm::types::{Call, ModulePackage, Transaction};
 
 #[test]
@@ -212,3 +214,18 @@ fn test_module_abi() {
         }
     );
 }
+
+#[test]
+fn test_balance_width() {
+    let balance = u64::MAX as Balance + 1;
+
+    let encoded = BalanceWidth::U128.encode(balance).unwrap();
+    assert_eq!(encoded, bcs::to_bytes(&balance).unwrap());
+    assert_eq!(BalanceWidth::U128.decode(&encoded), Some(balance));
+
+    assert!(BalanceWidth::U64.encode(balance).is_err());
+    let encoded = BalanceWidth::U64.encode(42).unwrap();
+    assert_eq!(encoded, bcs::to_bytes(&42u64).unwrap());
+    assert_eq!(BalanceWidth::U64.decode(&encoded), Some(42));
+    assert_eq!(BalanceWidth::U128.decode(&encoded), None);
+}
@@ -2,20 +2,44 @@
 extern crate alloc;
 
 use common::mock::Utils;
-use common::{assets::*, contains_core_module, mock::*, vm};
+use common::{
+    assets::*, contains_core_module, contains_native_functions, mock::*, vm, vm_with_gas_schedules,
+};
+use diem_crypto::ed25519::{Ed25519PublicKey, Ed25519Signature};
+use diem_crypto::multi_ed25519::{MultiEd25519PublicKey, MultiEd25519Signature};
+use diem_types::account_config::diem_root_address;
 use move_core_types::account_address::AccountAddress;
+use move_core_types::gas_schedule::{GasAlgebra, GasConstants, GasCost, InternalGasUnits};
 use move_core_types::identifier::Identifier;
 use move_core_types::language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS};
 use move_core_types::resolver::{ModuleResolver, ResourceResolver};
-use move_core_types::vm_status::{AbortLocation, StatusCode, VMStatus};
-use mvm::io::balance::CurrencyInfo;
-use mvm::io::context::ExecutionContext;
+use move_core_types::vm_status::{sub_status, AbortLocation, StatusCode, VMStatus};
+use move_vm_runtime::native_functions::{NativeContext, NativeFunction, NativeHost};
+use move_vm_types::loaded_data::runtime_types::Type;
+use move_vm_types::natives::function::{host_native_gas, NativeResult, PartialVMResult};
+use move_vm_types::values::Value;
+use mvm::error::{Category, SubStatus};
+use mvm::gas_schedule::{
+    cost_table, per_byte_gas_schedule, GasSchedules, WriteSetCosts, WriteSetSize,
+    DEFAULT_GAS_SCHEDULE_VERSION, PER_BYTE_GAS_SCHEDULE_VERSION,
+};
+use mvm::genesis::init_storage;
+use mvm::io::balance::{BalanceOp, CurrencyInfo, MasterOfCoin};
+use mvm::io::context::{ExecutionContext, VirtualResources};
 use mvm::io::state::State;
-use mvm::io::traits::BalanceAccess;
-use mvm::types::Gas;
+use mvm::io::traits::{Balance, BalanceAccess, FallibleStorage};
+use mvm::mvm::Mvm;
+use mvm::natives::{DebugOutput, HostNatives};
 use mvm::types::Transaction;
+use mvm::types::{Gas, StackFrame, StatusEvent, VmStatusEvent};
+use mvm::types::{ScriptArg, ScriptTx};
+use mvm::validation::ValidationError;
 use mvm::Vm;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::VecDeque;
 use std::convert::TryFrom;
+use std::rc::Rc;
 
 mod common;
 
@@ -61,10 +85,74 @@ fn test_run_module_function() {
 fn test_public_module_without_gas() {
     let (vm, _, _, _) = vm();
     let gas = Gas::new(1, 1).unwrap();
-    let res = vm.publish_module(gas, store_module(), false);
+    let res = vm.publish_module(gas, 0, store_module(), false);
     assert_eq!(res.status_code, StatusCode::OUT_OF_GAS);
 }
 
+#[test]
+fn test_write_set_gas() {
+    let vm = vm_with_gas_schedules(GasSchedules::new(per_byte_gas_schedule()));
+    let module = store_module();
+    let res = vm.publish_module(gas(), 0, module.clone(), true);
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    assert_eq!(res.gas_schedule_version, PER_BYTE_GAS_SCHEDULE_VERSION);
+
+    let constants = GasConstants::default();
+    let mut size = WriteSetSize::default();
+    size.add_write(None, module.code().len());
+    let write_gas = constants
+        .to_external_units(size.cost(&WriteSetCosts::default()))
+        .get();
+    assert!(write_gas > 0);
+    assert!(res.gas_used >= write_gas);
+    assert_eq!(res.gas_refund, 0);
+}
+
+#[test]
+fn test_gas_schedule_versions() {
+    let schedules = GasSchedules::default()
+        .with_version(10, per_byte_gas_schedule())
+        .unwrap();
+    let vm = vm_with_gas_schedules(schedules);
+
+    let res = vm.publish_module(gas(), 9, store_module(), false);
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    assert_eq!(res.gas_schedule_version, DEFAULT_GAS_SCHEDULE_VERSION);
+
+    let heights = [
+        (9, DEFAULT_GAS_SCHEDULE_VERSION),
+        (10, PER_BYTE_GAS_SCHEDULE_VERSION),
+        (9, DEFAULT_GAS_SCHEDULE_VERSION),
+    ];
+    let mut gas_used = vec![];
+    for &(height, version) in &heights {
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, height),
+            store_u64_script(addr("0x1"), 1),
+            true,
+        );
+        assert_eq!(res.status_code, StatusCode::EXECUTED);
+        assert_eq!(res.gas_schedule_version, version);
+        gas_used.push(res.gas_used);
+    }
+    // The legacy schedule charges `default_account_size` bytes for the mutated account
+    // instead of the few bytes of the stored value.
+    assert!(gas_used[0] > gas_used[1]);
+    assert_eq!(gas_used[0], gas_used[2]);
+
+    // The publications do not depend on the height of the previous transactions.
+    let mut gas_used = vec![];
+    for &(height, version) in &heights {
+        let res = vm.publish_module(Gas::infinite(), height, abort_module(), true);
+        assert_eq!(res.status_code, StatusCode::EXECUTED);
+        assert_eq!(res.gas_schedule_version, version);
+        gas_used.push(res.gas_used);
+    }
+    assert_ne!(gas_used[1], gas_used[0]);
+    assert_eq!(gas_used[0], gas_used[2]);
+}
+
 #[test]
 fn test_execute_script() {
     let test_value = 13;
@@ -90,6 +178,90 @@ fn test_execute_script() {
     assert_eq!(test_value, store.val);
 }
 
+#[test]
+fn test_storage_error() {
+    let (_, store, _, bank) = vm();
+    let store = FailingStorageMock::new(store);
+    let vm = Mvm::new(store.clone(), EventHandlerMock::default(), bank).unwrap();
+    vm.pub_mod(store_module());
+
+    store.fail_writes.set(true);
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        store_u64_script(addr("0x1"), 13),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::STORAGE_ERROR);
+
+    store.fail_writes.set(false);
+    store.fail_reads.set(true);
+    let vm = Mvm::new(store, EventHandlerMock::default(), BankMock::default()).unwrap();
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        store_u64_script(addr("0x1"), 13),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::STORAGE_ERROR);
+}
+
+#[test]
+fn test_storage_commit() {
+    let store = FailingStorageMock::new(StorageMock::new());
+    store.insert(b"a", b"a0").unwrap();
+    store.insert(b"b", b"b0").unwrap();
+    let snapshot = store.inner.data.borrow().clone();
+
+    store.inserts_left.set(Some(1));
+    let changes = vec![
+        (b"a".to_vec(), Some(b"a1".to_vec())),
+        (b"b".to_vec(), None),
+        (b"c".to_vec(), Some(b"c1".to_vec())),
+    ];
+    assert!(store.commit(changes.clone()).is_err());
+    assert_eq!(*store.inner.data.borrow(), snapshot);
+
+    store.commit(changes).unwrap();
+    assert_eq!(store.get(b"a").unwrap(), Some(b"a1".to_vec()));
+    assert_eq!(store.get(b"b").unwrap(), None);
+    assert_eq!(store.get(b"c").unwrap(), Some(b"c1".to_vec()));
+}
+
+#[test]
+fn test_partial_storage_error() {
+    let (_, store, _, bank) = vm();
+    let store = FailingStorageMock::new(store);
+    let event = EventHandlerMock::default();
+    let vm = Mvm::new(store.clone(), event.clone(), bank)
+        .unwrap()
+        .with_status_event(StatusEvent::Disabled);
+    vm.pub_mod(store_module());
+    let snapshot = store.inner.data.borrow().clone();
+    event.batches.borrow_mut().clear();
+
+    // The second resource write fails.
+    store.inserts_left.set(Some(1));
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        store_sys_resources_script(addr("0x1"), addr("0x2")),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::STORAGE_ERROR);
+    assert_eq!(*store.inner.data.borrow(), snapshot);
+    // No events of the failed transaction are delivered.
+    assert_eq!(event.batches.borrow().len(), 1);
+    assert!(event.batches.borrow()[0].1.is_empty());
+
+    store.inserts_left.set(None);
+    vm.exec_with_context(
+        ExecutionContext::new(100, 100),
+        store_sys_resources_script(addr("0x1"), addr("0x2")),
+    );
+    assert_eq!(store.inner.data.borrow().len(), snapshot.len() + 2);
+}
+
 #[test]
 fn test_store_event() {
     let test_value = 13;
@@ -119,6 +291,59 @@ fn test_store_event() {
     );
 }
 
+#[test]
+fn test_tx_events() {
+    let (vm, _, event, _) = vm();
+    vm.set_tx_index(10);
+    vm.pub_mod(event_proxy_module());
+    vm.exec(emit_event_script(addr("0x1"), 13));
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        emit_event_script(addr("0x1"), 14),
+        true,
+    );
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    vm.pub_mod(abort_module());
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        error_script(addr("0x1")),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+
+    let batches = event.batches.borrow();
+    let indexes = batches
+        .iter()
+        .map(|(tx_index, events)| {
+            (
+                *tx_index,
+                events.iter().map(|event| event.index).collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        indexes,
+        vec![(10, vec![]), (11, vec![0]), (12, vec![]), (13, vec![0])]
+    );
+    assert_eq!(
+        bcs::from_bytes::<StoreU64>(&batches[1].1[0].message)
+            .unwrap()
+            .val,
+        13
+    );
+    assert_eq!(
+        batches[3].1[0].ty_tag,
+        TypeTag::Struct(StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("VMStatus").unwrap(),
+            name: Identifier::new("VMStatusEvent").unwrap(),
+            type_params: vec![],
+        })
+    );
+}
+
 #[test]
 fn test_load_system_resources() {
     let (vm, store, _, _) = vm();
@@ -148,12 +373,139 @@ fn test_load_system_resources() {
     assert_eq!(store.val, timestamp);
 }
 
+#[test]
+fn test_context_resources() {
+    let state = State::new(StorageMock::new());
+    let master_of_coin = MasterOfCoin::new(BankMock::default());
+    let virtual_resources = VirtualResources::default();
+    let sender = AccountAddress::random();
+    let context = ExecutionContext {
+        sender: Some(sender),
+        ..ExecutionContext::new(10, 1000)
+            .with_parent_block_hash(vec![1; 32])
+            .with_tx_hash(vec![2; 32])
+            .with_chain_id(42)
+    };
+    let session = state.state_session(Some(context), &virtual_resources, &master_of_coin);
+
+    let resource = |module: &str, name: &str| {
+        let tag = StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new(module).unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params: vec![],
+        };
+        session
+            .get_resource(&diem_root_address(), &tag)
+            .unwrap()
+            .unwrap()
+    };
+
+    assert_eq!(
+        resource("PontTimestamp", "CurrentTimeMicroseconds"),
+        bcs::to_bytes(&10_u64).unwrap()
+    );
+    assert_eq!(
+        resource("PontBlock", "BlockMetadata"),
+        bcs::to_bytes(&1000_u64).unwrap()
+    );
+    assert_eq!(
+        resource("PontBlock", "ParentBlockHash"),
+        bcs::to_bytes(&vec![1_u8; 32]).unwrap()
+    );
+    assert_eq!(
+        resource("PontTransaction", "TransactionHash"),
+        bcs::to_bytes(&vec![2_u8; 32]).unwrap()
+    );
+    assert_eq!(
+        resource("PontTransaction", "TransactionSender"),
+        sender.to_vec()
+    );
+    assert_eq!(resource("PontChainId", "ChainId"), vec![42]);
+
+    let session = state.state_session(
+        Some(ExecutionContext::new(10, 1000)),
+        &virtual_resources,
+        &master_of_coin,
+    );
+    let tag = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("PontChainId").unwrap(),
+        name: Identifier::new("ChainId").unwrap(),
+        type_params: vec![],
+    };
+    assert_eq!(
+        session.get_resource(&diem_root_address(), &tag).unwrap(),
+        None
+    );
+}
+
+#[test]
+fn test_context_resources_script() {
+    let (vm, _, _, _) = vm();
+    let sender = addr("0x13");
+    // The sender is taken from the transaction signers.
+    let context = ExecutionContext {
+        sender: Some(addr("0x42")),
+        ..ExecutionContext::new(10, 1000)
+            .with_parent_block_hash(vec![1; 32])
+            .with_tx_hash(vec![2; 32])
+            .with_chain_id(42)
+    };
+    let res = vm.execute_script(
+        gas(),
+        context,
+        context_resources_script(sender, &[1; 32], &[2; 32], 42),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+}
+
+#[test]
+fn test_custom_virtual_resources() {
+    let state = State::new(StorageMock::new());
+    let master_of_coin = MasterOfCoin::new(BankMock::default());
+    let tag = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("Block").unwrap(),
+        name: Identifier::new("Height").unwrap(),
+        type_params: vec![],
+    };
+    let virtual_resources = VirtualResources::empty().with(addr("0x1"), tag.clone(), |ctx| {
+        bcs::to_bytes(&ctx.block_height).ok()
+    });
+    let session = state.state_session(
+        Some(ExecutionContext::new(10, 1000)),
+        &virtual_resources,
+        &master_of_coin,
+    );
+
+    assert_eq!(
+        session.get_resource(&addr("0x1"), &tag).unwrap(),
+        Some(bcs::to_bytes(&1000_u64).unwrap())
+    );
+    assert_eq!(session.get_resource(&addr("0x2"), &tag).unwrap(), None);
+
+    let timestamp = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("PontTimestamp").unwrap(),
+        name: Identifier::new("CurrentTimeMicroseconds").unwrap(),
+        type_params: vec![],
+    };
+    assert_eq!(
+        session
+            .get_resource(&diem_root_address(), &timestamp)
+            .unwrap(),
+        None
+    );
+}
+
 #[test]
 fn test_error_event() {
     let (vm, _, events, _) = vm();
     vm.pub_mod(abort_module());
     let sender = AccountAddress::random();
-    vm.execute_script(
+    let res = vm.execute_script(
         gas(),
         ExecutionContext::new(0, 0),
         error_script(sender),
@@ -161,7 +513,7 @@ fn test_error_event() {
     );
 
     let (guid, seq, tag, msg) = events.pop().unwrap();
-    let mut expected_guid = 0_u64.to_le_bytes().to_vec();
+    let mut expected_guid = 1_u64.to_le_bytes().to_vec();
     expected_guid.extend(sender.to_u8());
     assert_eq!(expected_guid, guid);
     assert_eq!(0, seq);
@@ -170,22 +522,89 @@ fn test_error_event() {
         TypeTag::Struct(StructTag {
             address: CORE_CODE_ADDRESS,
             module: Identifier::new("VMStatus").unwrap(),
-            name: Identifier::new("VMStatus").unwrap(),
+            name: Identifier::new("VMStatusEvent").unwrap(),
             type_params: vec![],
         }),
         tag
     );
-    let status = bcs::from_bytes::<VMStatus>(&msg).unwrap();
-    assert_eq!(
-        status,
-        VMStatus::MoveAbort(
-            AbortLocation::Module(ModuleId::new(
-                CORE_CODE_ADDRESS,
-                Identifier::new("Abort").unwrap(),
-            )),
-            13,
-        )
+    let event = bcs::from_bytes::<VmStatusEvent>(&msg).unwrap();
+    let location = AbortLocation::Module(ModuleId::new(
+        CORE_CODE_ADDRESS,
+        Identifier::new("Abort").unwrap(),
+    ));
+    assert_eq!(event.tx_index, 1);
+    assert_eq!(event.sender, sender);
+    assert_eq!(event.status, VMStatus::MoveAbort(location.clone(), 13));
+    assert_eq!(event.sub_status, Some(13));
+    assert_eq!(event.location, Some(location));
+    assert_eq!(event.gas_used, res.gas_used);
+}
+
+#[test]
+fn test_status_event_config() {
+    let tag = TypeTag::Struct(StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("Failure").unwrap(),
+        name: Identifier::new("Failure").unwrap(),
+        type_params: vec![],
+    });
+    let (vm, _, events, _) = vm();
+    let vm = vm.with_status_event(StatusEvent::Custom(tag.clone()));
+    vm.pub_mod(abort_module());
+    vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        error_script(AccountAddress::random()),
+        false,
     );
+    let (_, _, event_tag, _) = events.pop().unwrap();
+    assert_eq!(event_tag, tag);
+
+    let vm = vm.with_status_event(StatusEvent::Disabled);
+    vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        error_script(AccountAddress::random()),
+        false,
+    );
+    assert!(events.pop().is_none());
+}
+
+#[test]
+fn test_error_report() {
+    let (vm, _, _, _) = vm();
+    vm.pub_mod(abort_module());
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        error_script(AccountAddress::random()),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+    assert!(res.error_report.is_none());
+
+    let vm = vm.with_error_report(true);
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        error_script(AccountAddress::random()),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+    let report = res.error_report.unwrap();
+    let frame = StackFrame {
+        module: Some(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("Abort").unwrap(),
+        )),
+        function: Some(Identifier::new("error").unwrap()),
+        function_index: 0,
+        code_offset: 1,
+    };
+    assert_eq!(report.frame, Some(frame.clone()));
+    assert_eq!(report.stack_trace.len(), 2);
+    assert_eq!(report.stack_trace[0].module, None);
+    assert_eq!(report.stack_trace[1], frame);
 }
 
 #[test]
@@ -195,7 +614,7 @@ fn test_publish_pac() {
 
     let pac = valid_package().into_tx(CORE_CODE_ADDRESS);
 
-    let res = vm.publish_module_package(gas(), pac, false);
+    let res = vm.publish_module_package(gas(), 0, pac, false);
     if res.status_code != StatusCode::EXECUTED {
         panic!("Transaction failed: {:?}", res);
     }
@@ -210,7 +629,7 @@ fn test_publish_pac() {
 fn test_invalid_pac() {
     let (vm, _, _, _) = vm();
     let pac = invalid_package().into_tx(CORE_CODE_ADDRESS);
-    let res = vm.publish_module_package(gas(), pac, false);
+    let res = vm.publish_module_package(gas(), 0, pac, false);
     assert_eq!(res.status_code, StatusCode::LINKER_ERROR);
 }
 
@@ -273,6 +692,400 @@ fn test_balance() {
     );
 }
 
+#[test]
+fn test_balance_ops() {
+    let (vm, _, _, bank) = vm();
+    bank.set_currency_info("NOX".as_bytes(), CurrencyInfo { total_value: 1001 });
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    bank.set_balance(&alice, "NOX".as_bytes(), 1000);
+    bank.set_balance(&bob, "NOX".as_bytes(), 1);
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(alice, 1000, bob, 1, 500),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    assert_eq!(
+        res.balance_ops,
+        vec![
+            BalanceOp::Sub(alice, Cow::Owned(b"NOX".to_vec()), 500),
+            BalanceOp::Add(bob, Cow::Owned(b"NOX".to_vec()), 500),
+        ]
+    );
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(alice, 500, bob, 501, 100),
+        true,
+    );
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    assert!(res.balance_ops.is_empty());
+}
+
+#[test]
+fn test_rejected_balance_ops() {
+    let (vm, _, _, bank) = vm();
+    bank.set_currency_info("NOX".as_bytes(), CurrencyInfo { total_value: 1001 });
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    bank.set_balance(&alice, "NOX".as_bytes(), 1000);
+    bank.set_balance(&bob, "NOX".as_bytes(), 1);
+    bank.lock(&bob);
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(alice, 1000, bob, 1, 500),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::BALANCE_REJECTED);
+    assert!(res.balance_ops.is_empty());
+    assert_eq!(bank.get_balance(&alice, "NOX".as_bytes()), Some(1000));
+    assert_eq!(bank.get_balance(&bob, "NOX".as_bytes()), Some(1));
+}
+
+#[test]
+fn test_balance_overflow() {
+    let (vm, _, _, bank) = vm();
+    bank.set_currency_info("NOX".as_bytes(), CurrencyInfo { total_value: 1001 });
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    bank.set_balance(&alice, "NOX".as_bytes(), u64::MAX as Balance + 1);
+    bank.set_balance(&bob, "NOX".as_bytes(), 1);
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(alice, 0, bob, 1, 500),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::BALANCE_OUT_OF_RANGE);
+    assert!(res.balance_ops.is_empty());
+    assert_eq!(
+        bank.get_balance(&alice, "NOX".as_bytes()),
+        Some(u64::MAX as Balance + 1)
+    );
+    assert_eq!(bank.get_balance(&bob, "NOX".as_bytes()), Some(1));
+}
+
+#[test]
+fn test_failed_balance_revert() {
+    let (vm, _, _, bank) = vm();
+    bank.set_currency_info("NOX".as_bytes(), CurrencyInfo { total_value: 1001 });
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    bank.set_balance(&alice, "NOX".as_bytes(), 1000);
+    bank.set_balance(&bob, "NOX".as_bytes(), 1);
+    // The withdrawal is applied, the deposit and the revert of the withdrawal are rejected.
+    bank.ops_left.set(Some(1));
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(alice, 1000, bob, 1, 500),
+        false,
+    );
+    assert_eq!(
+        res.status_code,
+        StatusCode::UNKNOWN_INVARIANT_VIOLATION_ERROR
+    );
+    assert!(res.balance_ops.is_empty());
+    assert_eq!(bank.get_balance(&alice, "NOX".as_bytes()), Some(500));
+}
+
+#[test]
+fn test_native_bridges() {
+    let (vm, _, _, bank) = vm();
+    bank.set_currency_info("NOX".as_bytes(), CurrencyInfo { total_value: 1001 });
+    assert!(vm.native_bridges().is_empty());
+
+    let alice = AccountAddress::random();
+    let bob = AccountAddress::random();
+    bank.set_balance(&alice, "NOX".as_bytes(), 1000);
+    bank.set_balance(&bob, "NOX".as_bytes(), 1);
+    vm.exec(transfer_script(alice, 1000, bob, 1, 500));
+
+    let nox = StructTag {
+        address: CORE_CODE_ADDRESS,
+        module: Identifier::new("NOX").unwrap(),
+        name: Identifier::new("NOX").unwrap(),
+        type_params: vec![],
+    };
+    assert_eq!(vm.native_bridges(), vec![(nox.clone(), b"NOX".to_vec())]);
+
+    // The next transaction sees the bridge registered by the previous one.
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(100, 100),
+        transfer_script(bob, 501, alice, 500, 1),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::EXECUTED);
+    assert_eq!(
+        res.balance_ops,
+        vec![
+            BalanceOp::Sub(bob, Cow::Owned(b"NOX".to_vec()), 1),
+            BalanceOp::Add(alice, Cow::Owned(b"NOX".to_vec()), 1),
+        ]
+    );
+    assert_eq!(vm.native_bridges(), vec![(nox, b"NOX".to_vec())]);
+
+    vm.clear();
+    assert!(vm.native_bridges().is_empty());
+}
+
+#[test]
+fn test_vector_natives() {
+    let (vm, store, _, _) = vm();
+    let state = State::new(store);
+
+    contains_native_functions(
+        &state,
+        "Vector",
+        &["append", "reverse", "contains", "index_of", "remove"],
+    );
+
+    vm.exec(vector_natives_script());
+}
+
+#[test]
+fn test_u256_natives() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(
+        &State::new(store),
+        "U256",
+        &[
+            "mod",
+            "pow",
+            "mul_div",
+            "shl",
+            "shr",
+            "and",
+            "or",
+            "xor",
+            "lt",
+            "le",
+            "gt",
+            "ge",
+            "from_le_bytes",
+            "from_be_bytes",
+            "to_le_bytes",
+            "to_be_bytes",
+        ],
+    );
+
+    vm.exec(u256_natives_script());
+
+    let failures = vec![
+        sub_status::NFE_U256_MOD_BY_ZERO,
+        sub_status::NFE_U256_POW_OVERFLOW,
+        sub_status::NFE_U256_MUL_DIV_OVERFLOW,
+        sub_status::NFE_U256_MUL_DIV_BY_ZERO,
+        sub_status::NFE_U256_BYTES_LENGTH,
+    ];
+    for (case, code) in failures.into_iter().enumerate() {
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, 0),
+            u256_failure_script(case as u8),
+            false,
+        );
+        assert_eq!(res.status_code, StatusCode::ARITHMETIC_ERROR);
+        assert_eq!(res.sub_status, Some(SubStatus::new(code)));
+    }
+}
+
+#[test]
+fn test_bcs_from_bytes() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(&State::new(store), "BCS", &["from_bytes"]);
+
+    let values = vec![
+        (TypeTag::U64, bcs::to_bytes(&42u64).unwrap()),
+        (TypeTag::Bool, bcs::to_bytes(&true).unwrap()),
+        (TypeTag::Address, bcs::to_bytes(&addr("0x42")).unwrap()),
+        (
+            TypeTag::Vector(Box::new(TypeTag::U8)),
+            bcs::to_bytes(&vec![1u8, 2, 3]).unwrap(),
+        ),
+    ];
+    for (ty, bytes) in values {
+        vm.exec(bcs_from_bytes_script(ty, bytes));
+    }
+
+    let malformed = vec![
+        (TypeTag::U64, vec![1, 2, 3]),
+        (TypeTag::Bool, vec![2]),
+        (TypeTag::U8, vec![1, 2]),
+    ];
+    for (ty, bytes) in malformed {
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, 0),
+            bcs_from_bytes_script(ty, bytes),
+            false,
+        );
+        assert_eq!(res.status_code, StatusCode::ABORTED);
+        assert_eq!(
+            res.sub_status,
+            Some(SubStatus::new(sub_status::NFE_BCS_DESERIALIZATION_FAILURE))
+        );
+    }
+
+    // Signers can't be created from bytes.
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        bcs_from_bytes_script(TypeTag::Signer, addr("0x42").to_vec()),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::CONSTRAINT_NOT_SATISFIED);
+}
+
+#[test]
+fn test_hash_natives() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(&State::new(store), "Hash", &["keccak256", "blake2b_256"]);
+
+    let vectors = vec![
+        (
+            &b""[..],
+            "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+        ),
+        (
+            &b"abc"[..],
+            "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45",
+            "bddd813c634239723171ef3fee98579b94964e3bb1cb3e427262c8c068d52319",
+        ),
+        (
+            &b"The quick brown fox jumps over the lazy dog"[..],
+            "4d741b6f1eb29cb2a9b9911c82f56fa8d73b04959d3d9d222895df6c0b28aa15",
+            "01718cec35cd3d796dd00020e0bfecb473ad23457d063b75eff29c0ffa2e58a9",
+        ),
+    ];
+    for (data, keccak256, blake2b_256) in vectors {
+        vm.exec(hash_natives_script(
+            data,
+            &hex::decode(keccak256).unwrap(),
+            &hex::decode(blake2b_256).unwrap(),
+        ));
+    }
+}
+
+#[test]
+fn test_secp256k1() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(
+        &State::new(store),
+        "Signature",
+        &["secp256k1_recover", "secp256k1_verify"],
+    );
+
+    // Test vector of the Ethereum `ecrecover` precompile.
+    let hash =
+        hex::decode("456e9aea5e197a1f1af7a3e85a3212fa4049a3ba34c2289b4c860fc0b0c64ef3").unwrap();
+    let signature = hex::decode(
+        "9242685bf161793cc25603c231bc2f568eb630ea16aa137d2664ac8038825608\
+         4f8ae3bd7535248d0bd448298cc2e2071e56992d0774dc340c368ae950852ada1c",
+    )
+    .unwrap();
+    let pubkey = hex::decode(
+        "f57c1d4c961024e998eaec4b6bebec90e788ef5ade22e636ce76111b60db107d\
+         4c3404b9908a2f357c84ccb48cf412be41d09574a9291c9c7eb5173ccf2a339f",
+    )
+    .unwrap();
+    let mut other_hash = hash.clone();
+    other_hash[0] ^= 1;
+
+    vm.exec(secp256k1_script(&signature, &hash, &pubkey, true, true));
+    // The recovery id is required to recover the key but not to verify the signature.
+    vm.exec(secp256k1_script(
+        &signature[..64],
+        &hash,
+        &pubkey,
+        false,
+        true,
+    ));
+    vm.exec(secp256k1_script(
+        &signature,
+        &hash[..31],
+        &pubkey,
+        false,
+        false,
+    ));
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        secp256k1_script(&signature, &other_hash, &pubkey, true, false),
+        false,
+    );
+    // A signature of another message recovers another key.
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+    assert_eq!(res.sub_status, Some(SubStatus::new(2)));
+}
+
+#[test]
+fn test_ed25519_threshold() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(
+        &State::new(store),
+        "Signature",
+        &["ed25519_threshold_verify"],
+    );
+
+    // Keys of the `[1; 32]`, `[2; 32]` and `[3; 32]` seeds and their signatures of `threshold`.
+    let keys = vec![
+        "8a88e3dd7409f195fd52db2d3cba5d72ca6709bf1d94121bf3748801b40f6f5c",
+        "8139770ea87d175f56a35466c34c7ecccb8d8a91b4ee37a25df60f5b8fc9b394",
+        "ed4928c628d1c2c6eae90338905995612959273a5c63f93636c14614ac8737d1",
+    ];
+    let signatures = [
+        "267bc0b06a76cbdb73cb734072ca864fa0abee40e1f3397fff8c14d474f6264f\
+         3daa5525cf319fffce506872c20a51eb6439686bf2249b3c8be1ed57e8f9660b",
+        "93939274bedfc6dcd51f241cbc8f2cc6d6634cf5700d3df2b26e09ffae2822d7\
+         a25844e199271986ee9d1e82e5e54767656565673e95aad4bb30e81c7241310a",
+        "ec55cbafeaaf93ce7b62f1bd781fff3d9a47510d142148edd3b9692a99c456a5\
+         db5b86e2b20d9d0e327d7766be06fd4c52825b0cd75b0ce392097742e2198200",
+    ];
+    let keys = keys
+        .into_iter()
+        .map(|key| Ed25519PublicKey::try_from(&hex::decode(key).unwrap()[..]).unwrap())
+        .collect();
+    let public_key = MultiEd25519PublicKey::new(keys, 2).unwrap().to_bytes();
+    let signature = |signers: &[u8]| {
+        let signatures = signers
+            .iter()
+            .map(|&index| {
+                let sig = hex::decode(signatures[index as usize]).unwrap();
+                (Ed25519Signature::try_from(&sig[..]).unwrap(), index)
+            })
+            .collect();
+        MultiEd25519Signature::new(signatures).unwrap().to_bytes()
+    };
+
+    let cases = vec![
+        (signature(&[0, 2]), &b"threshold"[..], true),
+        (signature(&[0, 1, 2]), &b"threshold"[..], true),
+        (signature(&[1]), &b"threshold"[..], false),
+        (signature(&[0, 2]), &b"other"[..], false),
+        (vec![1, 2, 3], &b"threshold"[..], false),
+    ];
+    for (signature, message, valid) in cases {
+        vm.exec(ed25519_threshold_script(
+            &signature,
+            &public_key,
+            message,
+            valid,
+        ));
+    }
+}
+
 #[test]
 fn test_signer_order() {
     let (vm, _, _, _) = vm();
@@ -296,6 +1109,63 @@ fn test_reflect_type_of() {
     vm.exec(reflect_type_of(addr("0x1"), "EventProxy", "U64"));
 }
 
+#[test]
+fn test_reflect_type_name() {
+    let (vm, store, _, _) = vm();
+    contains_native_functions(
+        &State::new(store),
+        "Reflect",
+        &["type_name", "is_same_type"],
+    );
+
+    vm.pub_mod(store_module());
+
+    let store = |name: &str, type_params: Vec<TypeTag>| {
+        TypeTag::Struct(StructTag {
+            address: CORE_CODE_ADDRESS,
+            module: Identifier::new("Store").unwrap(),
+            name: Identifier::new(name).unwrap(),
+            type_params,
+        })
+    };
+    let vector = |ty: TypeTag| TypeTag::Vector(Box::new(ty));
+    let cases = vec![
+        (TypeTag::U64, TypeTag::U64, "u64", true),
+        (TypeTag::Bool, TypeTag::U8, "bool", false),
+        (TypeTag::Address, TypeTag::Signer, "address", false),
+        (TypeTag::Signer, TypeTag::Signer, "signer", true),
+        (vector(TypeTag::U8), vector(TypeTag::U8), "vector<u8>", true),
+        (
+            vector(vector(TypeTag::U64)),
+            vector(TypeTag::U64),
+            "vector<vector<u64>>",
+            false,
+        ),
+        (store("U64", vec![]), TypeTag::U64, "0x1::Store::U64", false),
+        (
+            store("Res", vec![store("U64", vec![])]),
+            store("Res", vec![store("U64", vec![])]),
+            "0x1::Store::Res<0x1::Store::U64>",
+            true,
+        ),
+        (
+            store("Res", vec![store("U64", vec![])]),
+            store("Res", vec![store("U128", vec![])]),
+            "0x1::Store::Res<0x1::Store::U64>",
+            false,
+        ),
+        (
+            vector(store("Res", vec![store("Address", vec![])])),
+            vector(store("Res", vec![store("Address", vec![])])),
+            "vector<0x1::Store::Res<0x1::Store::Address>>",
+            true,
+        ),
+    ];
+    for (ty, other, name, same_type) in cases {
+        vm.exec(reflect_type_name_script(ty, other, name, same_type));
+    }
+}
+
 #[test]
 #[should_panic]
 fn test_reflect_type_of_fail() {
@@ -304,3 +1174,322 @@ fn test_reflect_type_of_fail() {
     vm.pub_mod(event_proxy_module());
     vm.exec(reflect_type_of(addr("0x2"), "EventProxy", "U64"));
 }
+
+#[test]
+fn test_validate_script() {
+    let (vm, _, _, _) = vm();
+    vm.pub_mod(store_module());
+    assert_eq!(
+        vm.validate_script(&store_u64_script(addr("0x1"), 13)),
+        Ok(())
+    );
+
+    let code = include_bytes!("assets/build/assets/bytecode_scripts/store_u64.mv").to_vec();
+    let tx = ScriptTx::with_script(code.clone(), vec![ScriptArg::U64(13)], vec![], vec![]).unwrap();
+    assert_eq!(
+        vm.validate_script(&tx),
+        Err(ValidationError::SignersCountMismatch {
+            expected: 1,
+            actual: 0
+        })
+    );
+
+    let tx = ScriptTx::with_script(code.clone(), vec![], vec![], vec![addr("0x1")]).unwrap();
+    assert_eq!(
+        vm.validate_script(&tx),
+        Err(ValidationError::ArgumentsCountMismatch {
+            expected: 1,
+            actual: 0
+        })
+    );
+
+    let tx = ScriptTx::with_script(
+        code,
+        vec![ScriptArg::U128(13)],
+        vec![TypeTag::U8],
+        vec![addr("0x1")],
+    )
+    .unwrap();
+    assert_eq!(
+        vm.validate_script(&tx),
+        Err(ValidationError::TypeArgumentsCountMismatch {
+            expected: 0,
+            actual: 1
+        })
+    );
+
+    let tx = ScriptTx::with_script(
+        include_bytes!("assets/build/assets/bytecode_scripts/store_u64.mv").to_vec(),
+        vec![ScriptArg::U128(13)],
+        vec![],
+        vec![addr("0x1")],
+    )
+    .unwrap();
+    assert_eq!(
+        vm.validate_script(&tx),
+        Err(ValidationError::InvalidArgument {
+            position: 1,
+            expected: TypeTag::U64
+        })
+    );
+}
+
+#[test]
+fn test_validate_script_function() {
+    let tx = Transaction::try_from(
+        &include_bytes!("assets/build/assets/transaction/ScriptBook_test.mvt")[..],
+    )
+    .unwrap();
+    let script = tx.into_script(vec![]).unwrap();
+    let (vm, _, _, _) = vm();
+    assert_eq!(
+        vm.validate_script(&script),
+        Err(ValidationError::ModuleNotFound(ModuleId::new(
+            CORE_CODE_ADDRESS,
+            Identifier::new("ScriptBook").unwrap()
+        )))
+    );
+
+    vm.pub_mod(script_book_module());
+    assert_eq!(vm.validate_script(&script), Ok(()));
+
+    // A storage failure is not reported as a missing module.
+    let (_, store, _, bank) = common::vm();
+    let store = FailingStorageMock::new(store);
+    let vm = Mvm::new(store.clone(), EventHandlerMock::default(), bank).unwrap();
+    store.fail_reads.set(true);
+    match vm.validate_script(&script) {
+        Err(ValidationError::StorageError(id, _)) => {
+            assert_eq!(id.name().as_str(), "ScriptBook");
+        }
+        res => panic!("Unexpected validation result: {:?}", res),
+    }
+}
+
+fn host_noop(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    _args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let cost = host_native_gas(context.cost_table(), 0, 0)?;
+    NativeResult::map_partial_vm_result_empty(cost, Ok(()))
+}
+
+fn host_double(
+    context: &mut NativeContext,
+    _ty_args: Vec<Type>,
+    mut args: VecDeque<Value>,
+) -> PartialVMResult<NativeResult> {
+    let val = args.pop_back().unwrap().value_as::<u64>()?;
+    let cost = host_native_gas(context.cost_table(), 1, 0)?;
+    NativeResult::map_partial_vm_result_one(cost, Ok(Value::u64(val * 2)))
+}
+
+fn host_math_vm(double_cost: GasCost) -> Mvm<StorageMock, EventHandlerMock, BankMock> {
+    let store = StorageMock::new();
+    init_storage(store.clone(), Default::default()).unwrap();
+    let natives = HostNatives::new(
+        vec![(
+            CORE_CODE_ADDRESS,
+            Identifier::new("HostMath").unwrap(),
+            Identifier::new("double").unwrap(),
+            host_double,
+        )],
+        vec![GasCost::new(10, 1), double_cost],
+    );
+    let vm = Mvm::new_with_natives(
+        store,
+        EventHandlerMock::default(),
+        BankMock::default(),
+        natives,
+    )
+    .unwrap();
+    vm.pub_mod(host_math_module());
+    vm
+}
+
+#[test]
+fn test_host_natives() {
+    let natives = HostNatives::new(
+        vec![(
+            addr("0x2"),
+            Identifier::new("Host").unwrap(),
+            Identifier::new("noop").unwrap(),
+            host_noop,
+        )],
+        vec![GasCost::new(10, 1)],
+    );
+    assert!(Mvm::new_with_natives(
+        StorageMock::new(),
+        EventHandlerMock::default(),
+        BankMock::default(),
+        natives
+    )
+    .is_ok());
+
+    let natives = HostNatives::new(
+        vec![(
+            CORE_CODE_ADDRESS,
+            Identifier::new("Hash").unwrap(),
+            Identifier::new("sha2_256").unwrap(),
+            host_noop,
+        )],
+        vec![GasCost::new(10, 1)],
+    );
+    assert!(Mvm::new_with_natives(
+        StorageMock::new(),
+        EventHandlerMock::default(),
+        BankMock::default(),
+        natives
+    )
+    .is_err());
+
+    let noop = (
+        addr("0x2"),
+        Identifier::new("Host").unwrap(),
+        Identifier::new("noop").unwrap(),
+        host_noop as NativeFunction,
+    );
+    let natives = HostNatives::new(vec![noop.clone(), noop], vec![GasCost::new(10, 1)]);
+    assert!(Mvm::new_with_natives(
+        StorageMock::new(),
+        EventHandlerMock::default(),
+        BankMock::default(),
+        natives
+    )
+    .is_err());
+}
+
+#[test]
+fn test_host_native_call() {
+    let mut gas_used = vec![];
+    for &instruction_gas in &[0, 1_000_000] {
+        let vm = host_math_vm(GasCost::new(instruction_gas, 1));
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, 0),
+            host_double_script(21, 42),
+            false,
+        );
+        assert_eq!(res.status_code, StatusCode::EXECUTED);
+        gas_used.push(res.gas_used);
+
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, 0),
+            host_double_script(21, 43),
+            false,
+        );
+        assert_eq!(res.status_code, StatusCode::ABORTED);
+        assert_eq!(res.sub_status, Some(SubStatus::new(1)));
+    }
+
+    let constants = cost_table().gas_constants;
+    let native_gas = constants
+        .to_external_units(InternalGasUnits::new(1_000_000))
+        .get();
+    assert_eq!(gas_used[1] - gas_used[0], native_gas);
+}
+
+struct HostMock;
+
+impl NativeHost for HostMock {
+    fn query(&self, key: &[u8]) -> Result<Vec<u8>, u64> {
+        match key {
+            b"price" => Ok(100_u64.to_le_bytes().to_vec()),
+            _ => Err(0x0105),
+        }
+    }
+}
+
+#[test]
+fn test_host_query() {
+    let store = StorageMock::new();
+    init_storage(store.clone(), Default::default()).unwrap();
+    let vm = Mvm::new_with_natives(
+        store,
+        EventHandlerMock::default(),
+        BankMock::default(),
+        HostNatives::default().with_host(HostMock),
+    )
+    .unwrap();
+    vm.pub_mod(host_module());
+
+    vm.exec(host_query_script(b"price", &100_u64.to_le_bytes()));
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        host_query_script(b"validators", b""),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+    let sub_status = res.sub_status.unwrap();
+    assert!(matches!(sub_status.category, Category::NOT_PUBLISHED));
+    assert_eq!(sub_status.reason, 1);
+}
+
+#[test]
+fn test_host_query_without_host() {
+    let (vm, _, _, _) = vm();
+    vm.pub_mod(host_module());
+
+    let res = vm.execute_script(
+        gas(),
+        ExecutionContext::new(0, 0),
+        host_query_script(b"price", b""),
+        false,
+    );
+    assert_eq!(res.status_code, StatusCode::ABORTED);
+    let sub_status = res.sub_status.unwrap();
+    assert_eq!(
+        sub_status.reason,
+        sub_status::NFE_HOST_QUERY_UNSUPPORTED >> 8
+    );
+}
+
+#[test]
+fn test_debug_print() {
+    let printed = Rc::new(RefCell::new(Vec::new()));
+    let sink = printed.clone();
+    let debug = DebugOutput::Host(Box::new(
+        move |function: &str, ty: &TypeTag, value: &str| {
+            sink.borrow_mut()
+                .push((function.to_owned(), ty.clone(), value.to_owned()))
+        },
+    ));
+
+    let mut gas_used = vec![];
+    let outputs: Vec<_> = vec![DebugOutput::Disabled, DebugOutput::Log, debug];
+    for debug in outputs {
+        let store = StorageMock::new();
+        init_storage(store.clone(), Default::default()).unwrap();
+        let vm = Mvm::new_with_natives(
+            store,
+            EventHandlerMock::default(),
+            BankMock::default(),
+            HostNatives::default().with_debug(debug),
+        )
+        .unwrap();
+        let res = vm.execute_script(
+            gas(),
+            ExecutionContext::new(0, 0),
+            debug_print_script(42),
+            false,
+        );
+        assert_eq!(res.status_code, StatusCode::EXECUTED);
+        gas_used.push(res.gas_used);
+    }
+
+    let printed = printed.borrow();
+    assert_eq!(printed.len(), 2);
+    assert_eq!(
+        printed[0],
+        ("Script::main".to_owned(), TypeTag::U64, "42".to_owned())
+    );
+    let (function, ty, stack_trace) = &printed[1];
+    assert_eq!(function, "Script::main");
+    assert_eq!(ty, &TypeTag::Vector(Box::new(TypeTag::U8)));
+    assert!(!stack_trace.is_empty());
+    assert!(gas_used.iter().all(|gas| *gas == gas_used[0]));
+}
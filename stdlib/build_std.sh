@@ -28,13 +28,13 @@ lockfile_waithold
 rm -rf pont-stdlib
 git clone https://github.com/pontem-network/pont-stdlib.git
 cd pont-stdlib
-git reset --hard release-v1.0.0
+git reset --hard release-v1.1.0
 dove build -b
 cd ..
 
 rm -rf move-stdlib
 git clone https://github.com/pontem-network/move-stdlib.git
 cd move-stdlib
-git reset --hard release-v1.0.0
+git reset --hard release-v1.1.0
 dove build -b
 lockfile_release
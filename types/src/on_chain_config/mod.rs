@@ -116,7 +116,8 @@ impl fmt::Display for OnChainConfigPayload {
 
 /// Trait to be implemented by a storage type from which to read on-chain configs
 pub trait ConfigStorage {
-    fn fetch_config(&self, access_path: AccessPath) -> Option<Vec<u8>>;
+    /// Returns an error if the storage fails to read the config.
+    fn fetch_config(&self, access_path: AccessPath) -> Result<Option<Vec<u8>>>;
 }
 
 /// Trait to be implemented by a Rust struct representation of an on-chain config
@@ -147,13 +148,13 @@ pub trait OnChainConfig: Send + Sync + DeserializeOwned {
         Self::deserialize_default_impl(bytes)
     }
 
-    fn fetch_config<T>(storage: &T) -> Option<Self>
+    fn fetch_config<T>(storage: &T) -> Result<Option<Self>>
     where
         T: ConfigStorage,
     {
-        storage
-            .fetch_config(Self::CONFIG_ID.access_path())
-            .and_then(|bytes| Self::deserialize_into_config(&bytes).ok())
+        Ok(storage
+            .fetch_config(Self::CONFIG_ID.access_path())?
+            .and_then(|bytes| Self::deserialize_into_config(&bytes).ok()))
     }
 }
 